@@ -0,0 +1,359 @@
+// The Vec-based compression functions as they were before being moved onto
+// stack arrays, kept word for word so that the bench can time them against
+// the current ones.  Each is wrapped in just enough padding to give a whole
+// digest, which the bench checks against the crate's before timing.
+
+#![allow(clippy::identity_op, clippy::needless_range_loop)]
+
+const MD5_SHIFTS: &[u32] = &[
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+const MD5_K: &[u32] = &[
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+fn md5_compress(state: &mut [u32; 4], chunk: &[u8; 64]) {
+    // Little endian here too
+    let m: Vec<u32> = chunk
+        .chunks(4)
+        .map(|int32_bytes| {
+            ((int32_bytes[3] as u32) << 24)
+                | ((int32_bytes[2] as u32) << 16)
+                | ((int32_bytes[1] as u32) << 8)
+                | ((int32_bytes[0] as u32) << 0)
+        }).collect();
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+
+    for i in 0..64 {
+        let mut f;
+        let g;
+
+        if i <= 15 {
+            f = (b & c) | ((!b) & d);
+            g = i;
+        } else if i <= 31 {
+            f = (d & b) | ((!d) & c);
+            g = (5 * i + 1) % 16;
+        } else if i <= 47 {
+            f = b ^ c ^ d;
+            g = (3 * i + 5) % 16;
+        } else {
+            f = c ^ (b | (!d));
+            g = (7 * i) % 16;
+        }
+
+        f = f.wrapping_add(a).wrapping_add(MD5_K[i]).wrapping_add(m[g]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(f.rotate_left(MD5_SHIFTS[i]));
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+}
+
+fn sha1_compress(state: &mut [u32; 5], chunk: &[u8; 64]) {
+    let mut w: Vec<u32> = chunk
+        .chunks(4)
+        .map(|int32_bytes| {
+            ((int32_bytes[0] as u32) << 24)
+                | ((int32_bytes[1] as u32) << 16)
+                | ((int32_bytes[2] as u32) << 8)
+                | ((int32_bytes[3] as u32) << 0)
+        }).collect();
+
+    for i in 16..80 {
+        w.push(0);
+
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1)
+    }
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut e = state[4];
+
+    for i in 0..80 {
+        let f;
+        let k;
+
+        if i <= 19 {
+            f = (b & c) | ((!b) & d);
+            k = 0x5A827999;
+        } else if i <= 39 {
+            f = b ^ c ^ d;
+            k = 0x6ED9EBA1;
+        } else if i <= 59 {
+            f = (b & c) | (b & d) | (c & d);
+            k = 0x8F1BBCDC;
+        } else {
+            f = b ^ c ^ d;
+            k = 0xCA62C1D6;
+        }
+
+        let temp = a.rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(w[i]);
+
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+    state[4] = state[4].wrapping_add(e);
+}
+
+const SHA256_K: &[u32] = &[
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1,
+        0x923f82a4, 0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3,
+        0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786,
+        0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147,
+        0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13,
+        0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a,
+        0x5b9cca4f, 0x682e6ff3, 0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208,
+        0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+fn sha256_compress(state: &mut [u32; 8], chunk: &[u8; 64]) {
+    let mut w: Vec<u32> = chunk
+        .chunks(4)
+        .map(|int32_bytes| {
+            ((int32_bytes[0] as u32) << 24)
+                | ((int32_bytes[1] as u32) << 16)
+                | ((int32_bytes[2] as u32) << 8)
+                | ((int32_bytes[3] as u32) << 0)
+        }).collect();
+
+    w.resize(64, 0);
+
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut e = state[4];
+    let mut f = state[5];
+    let mut g = state[6];
+    let mut h = state[7];
+
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(SHA256_K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+    state[4] = state[4].wrapping_add(e);
+    state[5] = state[5].wrapping_add(f);
+    state[6] = state[6].wrapping_add(g);
+    state[7] = state[7].wrapping_add(h);
+}
+
+const IV: &[u64] = &[
+    0x6A09E667F3BCC908,
+    0xBB67AE8584CAA73B,
+    0x3C6EF372FE94F82B,
+    0xA54FF53A5F1D36F1,
+    0x510E527FADE682D1,
+    0x9B05688C2B3E6C1F,
+    0x1F83D9ABFB41BD6B,
+    0x5BE0CD19137E2179,
+];
+
+const SIGMA: &[&[usize]] = &[
+    &[0,  1,  2,  3,  4,  5,  6,  7,  8,  9,  10, 11, 12, 13, 14, 15],
+    &[14, 10, 4,  8,  9,  15, 13, 6,  1,  12, 0,  2,  11, 7,  5,  3 ],
+    &[11, 8,  12, 0,  5,  2,  15, 13, 10, 14, 3,  6,  7,  1,  9,  4 ],
+    &[7,  9,  3,  1,  13, 12, 11, 14, 2,  6,  5,  10, 4,  0,  15, 8 ],
+    &[9,  0,  5,  7,  2,  4,  10, 15, 14, 1,  11, 12, 6,  8,  3,  13],
+    &[2,  12, 6,  10, 0,  11, 8,  3,  4,  13, 7,  5,  15, 14, 1,  9 ],
+    &[12, 5,  1,  15, 14, 13, 4,  10, 0,  7,  6,  3,  9,  2,  8,  11],
+    &[13, 11, 7,  14, 12, 1,  3,  9,  5,  0,  15, 4,  8,  6,  2,  10],
+    &[6,  15, 14, 9,  11, 3,  0,  8,  12, 2,  13, 7,  1,  4,  10, 5 ],
+    &[10, 2,  8,  4,  7,  6,  1,  5,  15, 11, 9,  14, 3,  12, 13, 0 ],
+];
+
+fn mix(v: &mut [u64],
+       a: usize, b: usize, c: usize, d: usize,
+       x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+fn blake2b_compress(h: &mut [u64], chunk: &[u8], t: u128, is_last_block: bool, is_last_node: bool) {
+    let mut v = h.to_vec();
+    v.extend_from_slice(IV);
+
+    v[12] ^= ((t << 64) >> 64) as u64;
+    v[13] ^= (t >> 64) as u64;
+
+    if is_last_block {
+        v[14] = !v[14];
+    }
+
+    if is_last_node {
+        v[15] = !v[15];
+    }
+
+    let m: Vec<u64> = chunk.chunks(8).map(|eight_bytes| {
+        // little-endian u64
+        let mut result: u64 = 0;
+        for b in 0..8 {
+            result = (result << 8) | eight_bytes[7 - b] as u64
+        }
+
+        result
+    }).collect();
+
+    for i in 0..12 {
+        let s: &[usize] = SIGMA[i % 10];
+
+        mix(&mut v, 0, 4, 8,  12, m[s[0]], m[s[1]]);
+        mix(&mut v, 1, 5, 9,  13, m[s[2]], m[s[3]]);
+        mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+
+        mix(&mut v, 0, 5, 10, 15, m[s[8]],  m[s[9]]);
+        mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        mix(&mut v, 2, 7, 8,  13, m[s[12]], m[s[13]]);
+        mix(&mut v, 3, 4, 9,  14, m[s[14]], m[s[15]]);
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i];
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i + 8];
+    }
+}
+
+// Compress each whole block of `input`, then the rest with Merkle-Damgård
+// padding: a one bit, zeros up to 8 bytes short of a whole block, and the
+// length in bits
+fn md_blocks(input: &[u8], length: [u8; 8], mut compress: impl FnMut(&[u8; 64])) {
+    let whole = input.len() / 64 * 64;
+    for block in input[..whole].chunks_exact(64) {
+        compress(block.try_into().unwrap());
+    }
+
+    let mut tail = input[whole..].to_vec();
+    tail.push(0x80);
+    while tail.len() % 64 != 56 {
+        tail.push(0);
+    }
+    tail.extend_from_slice(&length);
+
+    for block in tail.chunks_exact(64) {
+        compress(block.try_into().unwrap());
+    }
+}
+
+pub fn md5(input: &[u8]) -> Vec<u8> {
+    let mut state = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+    md_blocks(input, (input.len() as u64 * 8).to_le_bytes(), |block| {
+        md5_compress(&mut state, block)
+    });
+    state.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+pub fn sha1(input: &[u8]) -> Vec<u8> {
+    let mut state = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    md_blocks(input, (input.len() as u64 * 8).to_be_bytes(), |block| {
+        sha1_compress(&mut state, block)
+    });
+    state.iter().flat_map(|v| v.to_be_bytes()).collect()
+}
+
+pub fn sha256(input: &[u8]) -> Vec<u8> {
+    let mut state = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+    md_blocks(input, (input.len() as u64 * 8).to_be_bytes(), |block| {
+        sha256_compress(&mut state, block)
+    });
+    state.iter().flat_map(|v| v.to_be_bytes()).collect()
+}
+
+// Unkeyed, with the full 64-byte digest
+pub fn blake2b(input: &[u8]) -> Vec<u8> {
+    let mut h = IV.to_vec();
+    h[0] ^= 0x01010000 ^ 64;
+
+    // The last block, even if whole, is kept back to be marked as the last
+    let full = input.len().saturating_sub(1) / 128;
+    for (i, block) in input[..full * 128].chunks_exact(128).enumerate() {
+        blake2b_compress(&mut h, block, (i as u128 + 1) * 128, false, false);
+    }
+
+    let mut last = [0; 128];
+    last[..input.len() - full * 128].copy_from_slice(&input[full * 128..]);
+    blake2b_compress(&mut h, &last, input.len() as u128, true, false);
+
+    h.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
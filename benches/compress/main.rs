@@ -0,0 +1,142 @@
+// Throughput of each compression function, through the incremental API with
+// long messages and through one-shot `digest` with short ones (where the
+// per-block overheads show most).  Run with `cargo bench`; `cargo bench --
+// sha` limits it to the algorithms whose names contain "sha".
+//
+// After those come MD5, SHA-1, SHA-256 and BLAKE2b against the Vec-based
+// compression functions they had before (in before.rs), on the
+// same messages.
+
+mod before;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use hash_functions::blake2::{Blake2b, Blake2s};
+use hash_functions::blake3::Blake3;
+use hash_functions::keccak::Keccak256;
+use hash_functions::md4::Md4;
+use hash_functions::md5::Md5;
+use hash_functions::ripemd128::Ripemd128;
+use hash_functions::ripemd160::Ripemd160;
+use hash_functions::ripemd256::Ripemd256;
+use hash_functions::ripemd320::Ripemd320;
+use hash_functions::sha1::Sha1;
+use hash_functions::sha256::{Sha224, Sha256};
+use hash_functions::sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use hash_functions::sha512::{Sha384, Sha512};
+use hash_functions::Digest;
+
+const RUN_TIME: Duration = Duration::from_millis(500);
+
+// Call `f` for RUN_TIME, returning the rate in MB/s, given that each call
+// hashes `bytes` bytes
+fn rate(bytes: usize, mut f: impl FnMut()) -> f64 {
+    let start = Instant::now();
+    let mut calls = 0u64;
+
+    while start.elapsed() < RUN_TIME {
+        f();
+        calls += 1;
+    }
+
+    (calls * bytes as u64) as f64 / start.elapsed().as_secs_f64() / 1e6
+}
+
+fn selected(name: &str, filter: &[String]) -> bool {
+    let name = name.to_ascii_lowercase();
+    filter.is_empty() || filter.iter().any(|f| name.contains(&f.to_ascii_lowercase()))
+}
+
+fn bench<D: Digest>(filter: &[String]) {
+    if !selected(D::NAME, filter) {
+        return;
+    }
+
+    let long = vec![0x5a; 1 << 20];
+    let long_rate = rate(long.len(), || {
+        let mut hasher = D::new();
+        hasher.update(black_box(&long));
+        black_box(hasher.finalize());
+    });
+
+    let short = [0x5a; 64];
+    let short_rate = rate(short.len(), || {
+        black_box(D::digest(black_box(&short)));
+    });
+
+    println!("{:<12} {:>10.1} {:>10.1}", D::NAME, long_rate, short_rate);
+}
+
+// The old one-shot `before` against `D::digest`, which must agree, for the
+// same 1 MiB and 64-byte messages as above
+fn compare<D: Digest>(before: fn(&[u8]) -> Vec<u8>, filter: &[String]) {
+    if !selected(D::NAME, filter) {
+        return;
+    }
+
+    let long = vec![0x5a; 1 << 20];
+    let short = [0x5a; 64];
+    for message in [&long[..], &short, b"", &long[..1000]] {
+        assert_eq!(before(message), D::digest(message).as_ref(), "{}", D::NAME);
+    }
+
+    let rates: Vec<f64> = [&long[..], &short]
+        .iter()
+        .flat_map(|&message| {
+            [
+                rate(message.len(), || {
+                    black_box(before(black_box(message)));
+                }),
+                rate(message.len(), || {
+                    black_box(D::digest(black_box(message)));
+                }),
+            ]
+        })
+        .collect();
+
+    println!(
+        "{:<12} {:>10.1} {:>10.1} {:>10.1} {:>10.1}",
+        D::NAME, rates[0], rates[1], rates[2], rates[3]
+    );
+}
+
+fn main() {
+    // `cargo bench` passes --bench; anything else picks algorithms
+    let filter: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+
+    println!("{:<12} {:>10} {:>10}", "", "1 MiB", "64 B");
+    println!("{:<12} {:>10} {:>10}", "algorithm", "MB/s", "MB/s");
+
+    bench::<Md4>(&filter);
+    bench::<Md5>(&filter);
+    bench::<Sha1>(&filter);
+    bench::<Sha224>(&filter);
+    bench::<Sha256>(&filter);
+    bench::<Sha384>(&filter);
+    bench::<Sha512>(&filter);
+    bench::<Sha3_224>(&filter);
+    bench::<Sha3_256>(&filter);
+    bench::<Sha3_384>(&filter);
+    bench::<Sha3_512>(&filter);
+    bench::<Keccak256>(&filter);
+    bench::<Blake2b>(&filter);
+    bench::<Blake2s>(&filter);
+    bench::<Blake3>(&filter);
+    bench::<Ripemd128>(&filter);
+    bench::<Ripemd160>(&filter);
+    bench::<Ripemd256>(&filter);
+    bench::<Ripemd320>(&filter);
+
+    println!();
+    println!("{:<12} {:>21} {:>21}", "", "1 MiB", "64 B");
+    println!("{:<12} {:>10} {:>10} {:>10} {:>10}", "algorithm", "before", "after", "before", "after");
+
+    compare::<Md5>(before::md5, &filter);
+    compare::<Sha1>(before::sha1, &filter);
+    compare::<Sha256>(before::sha256, &filter);
+    compare::<Blake2b>(before::blake2b, &filter);
+}
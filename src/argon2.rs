@@ -0,0 +1,806 @@
+//! Argon2, the memory-hard password hash of RFC 9106, in its data-dependent
+//! (Argon2d), data-independent (Argon2i) and hybrid (Argon2id) variants.
+//!
+//! Everything is built on BLAKE2b: the initial hashes and the tag come from
+//! [`Blake2b`], and the compression function that fills memory runs
+//! BLAKE2b's G function with a multiplication worked into its additions.
+//!
+//! ```
+//! use hash_functions::argon2::{Params, PasswordHash, Variant};
+//!
+//! let params = Params::new(Variant::Argon2id).memory_cost(256).time_cost(2)?;
+//! let hash = params.hash_password(b"hunter2", b"saltsaltsalt")?;
+//!
+//! let stored = hash.to_string();
+//! assert!(stored.starts_with("$argon2id$v=19$m=256,t=2,p=4$"));
+//!
+//! let hash: PasswordHash = stored.parse()?;
+//! assert!(hash.verify(b"hunter2"));
+//! assert!(!hash.verify(b"hunter3"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::thread;
+
+use crate::blake2::{self, mix_with, Blake2b};
+use crate::ct_eq;
+
+// Version 1.3, the only one RFC 9106 describes: later passes XOR new blocks
+// into the old ones rather than overwriting them.
+const VERSION: u32 = 0x13;
+
+// Each lane is split into this many segments, which are filled in step
+// across the lanes.
+const SYNC_POINTS: usize = 4;
+
+const MIN_SALT_LENGTH: usize = 8;
+const MIN_OUTPUT_LENGTH: usize = 4;
+const MAX_PARALLELISM: u32 = (1 << 24) - 1;
+
+/// The most memory, in KiB, that a parsed [`PasswordHash`] may ask for:
+/// 2 GiB, RFC 9106's first recommended setting.  The format allows up to
+/// 4 TiB, which [`PasswordHash::verify`] would try to allocate.
+pub const MAX_PARSED_MEMORY_COST: u32 = 2 * 1024 * 1024;
+
+/// The most passes over memory that a parsed [`PasswordHash`] may ask for.
+pub const MAX_PARSED_TIME_COST: u32 = 1024;
+
+/// The most lanes that a parsed [`PasswordHash`] may ask for, each of which
+/// [`PasswordHash::verify`] runs on a thread of its own.
+pub const MAX_PARSED_PARALLELISM: u32 = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Memory accesses depend on the password: fastest to compute and
+    /// hardest to crack with custom hardware, but open to side channels.
+    Argon2d,
+    /// Memory accesses depend only on the parameters, never on the
+    /// password.
+    Argon2i,
+    /// Argon2i for the first half of the first pass, Argon2d after that.
+    /// RFC 9106 recommends this one.
+    Argon2id,
+}
+
+impl Variant {
+    /// The name PHC strings use, e.g. `argon2id`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::Argon2d => "argon2d",
+            Variant::Argon2i => "argon2i",
+            Variant::Argon2id => "argon2id",
+        }
+    }
+
+    fn code(self) -> u32 {
+        match self {
+            Variant::Argon2d => 0,
+            Variant::Argon2i => 1,
+            Variant::Argon2id => 2,
+        }
+    }
+}
+
+/// Argon2's parameters: which variant, how much memory and time to spend,
+/// and any secret key or associated data.
+///
+/// The defaults are RFC 9106's second recommended setting: 64 MiB of
+/// memory, three passes, four lanes and a 32-byte tag.  As with
+/// [`blake2::Params`], each setter returns the updated parameters so that
+/// they chain, in a `Result` where some values aren't allowed.  The memory
+/// cost depends on the number of lanes, so is checked when hashing.
+#[derive(Clone)]
+pub struct Params {
+    variant: Variant,
+    memory_cost: u32,
+    time_cost: u32,
+    parallelism: u32,
+    threads: Option<usize>,
+    output_length: usize,
+    secret: Vec<u8>,
+    associated_data: Vec<u8>,
+}
+
+impl Params {
+    pub fn new(variant: Variant) -> Params {
+        Params {
+            variant,
+            memory_cost: 64 * 1024,
+            time_cost: 3,
+            parallelism: 4,
+            threads: None,
+            output_length: 32,
+            secret: Vec::new(),
+            associated_data: Vec::new(),
+        }
+    }
+
+    /// In KiB.  At least 8 per lane: [`Params::hash`] refuses any less.
+    /// Rounded down to a multiple of 4 per lane in use.
+    pub fn memory_cost(mut self, kib: u32) -> Params {
+        self.memory_cost = kib;
+        self
+    }
+
+    /// The number of passes over memory, at least 1.
+    pub fn time_cost(mut self, passes: u32) -> Result<Params, InvalidParameter> {
+        if passes == 0 {
+            return Err(InvalidParameter::TimeCost);
+        }
+
+        self.time_cost = passes;
+        Ok(self)
+    }
+
+    /// The number of lanes, between 1 and 2^24 - 1.  Each lane can be
+    /// filled on a thread of its own.
+    pub fn parallelism(mut self, lanes: u32) -> Result<Params, InvalidParameter> {
+        if !(1..=MAX_PARALLELISM).contains(&lanes) {
+            return Err(InvalidParameter::Parallelism(lanes));
+        }
+
+        self.parallelism = lanes;
+        Ok(self)
+    }
+
+    /// How many threads to share the lanes among: by default, one per lane.
+    /// This only changes how fast the hash is computed, never its value.
+    pub fn threads(mut self, threads: usize) -> Result<Params, InvalidParameter> {
+        if threads == 0 {
+            return Err(InvalidParameter::Threads);
+        }
+
+        self.threads = Some(threads);
+        Ok(self)
+    }
+
+    /// At least 4 bytes.
+    pub fn output_length(mut self, length: usize) -> Result<Params, InvalidParameter> {
+        if length < MIN_OUTPUT_LENGTH || length > u32::MAX as usize {
+            return Err(InvalidParameter::OutputLength(length));
+        }
+
+        self.output_length = length;
+        Ok(self)
+    }
+
+    /// A key kept apart from the stored hashes, such as a pepper.
+    pub fn secret(mut self, secret: &[u8]) -> Params {
+        self.secret = secret.to_vec();
+        self
+    }
+
+    /// Data to bind into the hash, not kept secret.
+    pub fn associated_data(mut self, data: &[u8]) -> Params {
+        self.associated_data = data.to_vec();
+        self
+    }
+
+    /// The tag for `password` and `salt`, which has to be at least 8 bytes
+    /// long.  An error too if the memory cost is below 8 KiB per lane.
+    pub fn hash(&self, password: &[u8], salt: &[u8]) -> Result<Vec<u8>, InvalidParameter> {
+        if salt.len() < MIN_SALT_LENGTH {
+            return Err(InvalidParameter::SaltLength(salt.len()));
+        }
+        if (self.memory_cost as u64) < 8 * self.parallelism as u64 {
+            return Err(InvalidParameter::MemoryCost(self.memory_cost));
+        }
+
+        let h0 = self.initial_hash(password, salt);
+        let instance = Instance::new(self);
+
+        let mut memory = vec![Block::ZERO; instance.blocks];
+
+        // The first two blocks of each lane come straight from H0
+        for lane in 0..instance.lanes {
+            for i in 0..2 {
+                let mut bytes = [0; 1024];
+                long_hash(
+                    &[&h0, &(i as u32).to_le_bytes(), &(lane as u32).to_le_bytes()],
+                    &mut bytes,
+                );
+                memory[lane * instance.lane_length + i] = Block::from_bytes(&bytes);
+            }
+        }
+
+        instance.fill_memory(&mut memory);
+
+        // The tag is drawn from the last block of every lane, XORed together
+        let mut last = memory[instance.lane_length - 1];
+        for lane in 1..instance.lanes {
+            last.xor(&memory[(lane + 1) * instance.lane_length - 1]);
+        }
+
+        let mut tag = vec![0; self.output_length];
+        long_hash(&[&last.to_bytes()], &mut tag);
+        Ok(tag)
+    }
+
+    /// The tag for `password` and `salt`, along with everything needed to
+    /// check a password against it later except the secret.
+    pub fn hash_password(&self, password: &[u8], salt: &[u8]) -> Result<PasswordHash, InvalidParameter> {
+        Ok(PasswordHash {
+            params: self.clone(),
+            salt: salt.to_vec(),
+            hash: self.hash(password, salt)?,
+        })
+    }
+
+    // H0: every input, each length-prefixed, hashed together
+    fn initial_hash(&self, password: &[u8], salt: &[u8]) -> [u8; 64] {
+        let mut state = Blake2b::new();
+
+        for value in [
+            self.parallelism,
+            self.output_length as u32,
+            self.memory_cost,
+            self.time_cost,
+            VERSION,
+            self.variant.code(),
+        ] {
+            state.update(&value.to_le_bytes());
+        }
+
+        for input in [password, salt, &self.secret, &self.associated_data] {
+            state.update(&(input.len() as u32).to_le_bytes());
+            state.update(input);
+        }
+
+        let mut h0 = [0; 64];
+        h0.copy_from_slice(state.finalize().as_bytes());
+        h0
+    }
+}
+
+// H': BLAKE2b stretched to any output length.  Past 64 bytes, each hash is
+// of the one before, and contributes only its first half until the last.
+// Every length asked of BLAKE2b is between 1 and 64, since `out` is never
+// empty.
+fn long_hash(input: &[&[u8]], mut out: &mut [u8]) {
+    let length = (out.len() as u32).to_le_bytes();
+
+    let mut state = blake2::Params::new().digest_length(out.len().min(64)).unwrap().to_state();
+    state.update(&length);
+    for part in input {
+        state.update(part);
+    }
+    let mut v = state.finalize();
+
+    while out.len() > 64 {
+        out[..32].copy_from_slice(&v.as_bytes()[..32]);
+        out = &mut out[32..];
+        v = blake2::blake2(v.as_bytes(), None, out.len().min(64)).unwrap();
+    }
+
+    out.copy_from_slice(v.as_bytes());
+}
+
+/// One kibibyte of Argon2 memory.
+#[derive(Clone, Copy)]
+struct Block([u64; 128]);
+
+impl Block {
+    const ZERO: Block = Block([0; 128]);
+
+    fn from_bytes(bytes: &[u8; 1024]) -> Block {
+        let mut block = Block::ZERO;
+        for (word, eight_bytes) in block.0.iter_mut().zip(bytes.chunks_exact(8)) {
+            *word = u64::from_le_bytes(eight_bytes.try_into().unwrap());
+        }
+        block
+    }
+
+    fn to_bytes(self) -> [u8; 1024] {
+        let mut bytes = [0; 1024];
+        for (eight_bytes, word) in bytes.chunks_exact_mut(8).zip(&self.0) {
+            eight_bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn xor(&mut self, other: &Block) {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a ^= b;
+        }
+    }
+}
+
+// BLAKE2b's addition with twice the product of the low halves added in:
+// the "BlaMka" tweak that makes G cost multiplications too.
+fn blamka(a: u64, b: u64) -> u64 {
+    let product = (a & 0xffffffff).wrapping_mul(b & 0xffffffff);
+    a.wrapping_add(b).wrapping_add(product.wrapping_mul(2))
+}
+
+// P: one round of BLAKE2b, columns then diagonals, over sixteen words
+fn permute(v: &mut [u64; 16]) {
+    mix_with(v, 0, 4, 8,  12, 0, 0, blamka);
+    mix_with(v, 1, 5, 9,  13, 0, 0, blamka);
+    mix_with(v, 2, 6, 10, 14, 0, 0, blamka);
+    mix_with(v, 3, 7, 11, 15, 0, 0, blamka);
+
+    mix_with(v, 0, 5, 10, 15, 0, 0, blamka);
+    mix_with(v, 1, 6, 11, 12, 0, 0, blamka);
+    mix_with(v, 2, 7, 8,  13, 0, 0, blamka);
+    mix_with(v, 3, 4, 9,  14, 0, 0, blamka);
+}
+
+// G, the compression function.  X XOR Y is treated as an 8x8 matrix of
+// 16-byte registers: P runs over each row and then each column, and the
+// result is XORed with X XOR Y once more.
+fn compress(x: &Block, y: &Block) -> Block {
+    let mut r = *x;
+    r.xor(y);
+
+    let mut q = r;
+
+    for row in q.0.chunks_exact_mut(16) {
+        let row: &mut [u64; 16] = row.try_into().unwrap();
+        permute(row);
+    }
+
+    for column in 0..8 {
+        let mut v = [0; 16];
+        for i in 0..8 {
+            v[2 * i] = q.0[16 * i + 2 * column];
+            v[2 * i + 1] = q.0[16 * i + 2 * column + 1];
+        }
+
+        permute(&mut v);
+
+        for i in 0..8 {
+            q.0[16 * i + 2 * column] = v[2 * i];
+            q.0[16 * i + 2 * column + 1] = v[2 * i + 1];
+        }
+    }
+
+    q.xor(&r);
+    q
+}
+
+// The shape of memory for one hash, worked out from its parameters
+struct Instance {
+    variant: Variant,
+    passes: u32,
+    lanes: usize,
+    threads: usize,
+    blocks: usize,
+    lane_length: usize,
+    segment_length: usize,
+}
+
+impl Instance {
+    fn new(params: &Params) -> Instance {
+        let lanes = params.parallelism as usize;
+        let blocks = params.memory_cost as usize / (SYNC_POINTS * lanes) * (SYNC_POINTS * lanes);
+        let lane_length = blocks / lanes;
+
+        Instance {
+            variant: params.variant,
+            passes: params.time_cost,
+            lanes,
+            threads: params.threads.unwrap_or(lanes).min(lanes),
+            blocks,
+            lane_length,
+            segment_length: lane_length / SYNC_POINTS,
+        }
+    }
+
+    // Fill memory a slice at a time.  Within a slice, no lane reads the
+    // segment another lane is filling, so the segments can be computed
+    // side by side from a shared view of memory, then copied in together.
+    fn fill_memory(&self, memory: &mut [Block]) {
+        for pass in 0..self.passes {
+            for slice in 0..SYNC_POINTS {
+                let shared: &[Block] = memory;
+
+                let segments: Vec<(usize, Vec<Block>)> = if self.threads == 1 {
+                    (0..self.lanes)
+                        .map(|lane| (lane, self.fill_segment(shared, pass, slice, lane)))
+                        .collect()
+                } else {
+                    thread::scope(|scope| {
+                        let workers: Vec<_> = (0..self.threads)
+                            .map(|first| {
+                                scope.spawn(move || {
+                                    (first..self.lanes)
+                                        .step_by(self.threads)
+                                        .map(|lane| (lane, self.fill_segment(shared, pass, slice, lane)))
+                                        .collect::<Vec<_>>()
+                                })
+                            })
+                            .collect();
+
+                        workers
+                            .into_iter()
+                            .flat_map(|worker| worker.join().unwrap())
+                            .collect()
+                    })
+                };
+
+                for (lane, segment) in segments {
+                    let start = lane * self.lane_length + slice * self.segment_length;
+                    memory[start..start + self.segment_length].copy_from_slice(&segment);
+                }
+            }
+        }
+    }
+
+    // Compute one segment's new blocks.  Those already made in this
+    // segment come from `segment`; everything else from `memory`.
+    fn fill_segment(&self, memory: &[Block], pass: u32, slice: usize, lane: usize) -> Vec<Block> {
+        let start = lane * self.lane_length + slice * self.segment_length;
+        let block = |segment: &[Block], index: usize| -> Block {
+            if (start..start + segment.len()).contains(&index) {
+                segment[index - start]
+            } else {
+                memory[index]
+            }
+        };
+
+        let data_independent = match self.variant {
+            Variant::Argon2d => false,
+            Variant::Argon2i => true,
+            Variant::Argon2id => pass == 0 && slice < SYNC_POINTS / 2,
+        };
+
+        // Argon2i draws its pseudorandom numbers 128 at a time from a block
+        // that G makes out of a counter and the position in memory
+        let mut input = Block::ZERO;
+        let mut addresses = Block::ZERO;
+        if data_independent {
+            input.0[0] = pass as u64;
+            input.0[1] = lane as u64;
+            input.0[2] = slice as u64;
+            input.0[3] = self.blocks as u64;
+            input.0[4] = self.passes as u64;
+            input.0[5] = self.variant.code() as u64;
+        }
+
+        // The first two blocks of each lane are already there
+        let first = if pass == 0 && slice == 0 { 2 } else { 0 };
+
+        let mut segment: Vec<Block> = memory[start..start + first].to_vec();
+        segment.reserve(self.segment_length - first);
+
+        for i in first..self.segment_length {
+            let current = start + i;
+            let previous = if current.is_multiple_of(self.lane_length) {
+                current + self.lane_length - 1
+            } else {
+                current - 1
+            };
+
+            let previous = block(&segment, previous);
+
+            let pseudo_random = if data_independent {
+                if i % 128 == 0 || i == first {
+                    input.0[6] += 1;
+                    addresses = compress(&Block::ZERO, &compress(&Block::ZERO, &input));
+                }
+                addresses.0[i % 128]
+            } else {
+                previous.0[0]
+            };
+
+            let reference = self.reference_index(pass, slice, lane, i, pseudo_random);
+
+            let mut new = compress(&previous, &block(&segment, reference));
+            if pass > 0 {
+                new.xor(&memory[current]);
+            }
+            segment.push(new);
+        }
+
+        segment
+    }
+
+    // Which block the one at `index` in this segment mixes in: a lane
+    // chosen by the high half of `pseudo_random`, then a block in that lane
+    // by the low half, biased towards the most recently filled.
+    fn reference_index(&self, pass: u32, slice: usize, lane: usize, index: usize, pseudo_random: u64) -> usize {
+        let j1 = pseudo_random & 0xffffffff;
+        let j2 = pseudo_random >> 32;
+
+        let reference_lane = if pass == 0 && slice == 0 {
+            lane
+        } else {
+            (j2 % self.lanes as u64) as usize
+        };
+        let same_lane = reference_lane == lane;
+
+        // The blocks to choose from: everything finished so far, except
+        // the previous block (which is mixed in anyway) and, in other
+        // lanes, the current slice
+        let finished = if pass == 0 {
+            slice * self.segment_length
+        } else {
+            self.lane_length - self.segment_length
+        };
+        let area = if same_lane {
+            finished + index - 1
+        } else if index == 0 {
+            finished - 1
+        } else {
+            finished
+        };
+
+        let x = (j1 * j1) >> 32;
+        let y = (area as u64 * x) >> 32;
+        let relative = area - 1 - y as usize;
+
+        let area_start = if pass == 0 || slice == SYNC_POINTS - 1 {
+            0
+        } else {
+            (slice + 1) * self.segment_length
+        };
+
+        reference_lane * self.lane_length + (area_start + relative) % self.lane_length
+    }
+}
+
+/// An Argon2 hash with its salt and parameters, in the PHC string format
+/// that password databases store:
+///
+/// ```text
+/// $argon2id$v=19$m=65536,t=3,p=4$c2FsdHNhbHRzYWx0$gtE1KXk...
+/// ```
+///
+/// The salt and the hash are in unpadded base64.  Associated data goes in
+/// a `data` parameter when there is any.  A secret never appears in the
+/// string: to check a hash made with one, set it on `params` after parsing.
+#[derive(Clone)]
+pub struct PasswordHash {
+    pub params: Params,
+    pub salt: Vec<u8>,
+    pub hash: Vec<u8>,
+}
+
+impl PasswordHash {
+    /// Whether `password` gives this hash, compared in constant time.
+    /// Never, if the fields have been set to parameters that can't be
+    /// hashed with.
+    ///
+    /// This costs as much memory and time as the parameters say, which is
+    /// why parsing puts limits on them.
+    pub fn verify(&self, password: &[u8]) -> bool {
+        let Ok(params) = self.params.clone().output_length(self.hash.len()) else {
+            return false;
+        };
+
+        match params.hash(password, &self.salt) {
+            Ok(hash) => ct_eq(&hash, &self.hash),
+            Err(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for PasswordHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "${}$v={}$m={},t={},p={}",
+            self.params.variant.name(),
+            VERSION,
+            self.params.memory_cost,
+            self.params.time_cost,
+            self.params.parallelism
+        )?;
+
+        if !self.params.associated_data.is_empty() {
+            write!(f, ",data={}", base64_encode(&self.params.associated_data))?;
+        }
+
+        write!(f, "${}${}", base64_encode(&self.salt), base64_encode(&self.hash))
+    }
+}
+
+impl FromStr for PasswordHash {
+    type Err = ParsePasswordHashError;
+
+    fn from_str(s: &str) -> Result<PasswordHash, ParsePasswordHashError> {
+        use ParsePasswordHashError::*;
+
+        let fields: Vec<&str> = s.split('$').collect();
+        let [empty, variant, version, parameters, salt, hash] = fields[..] else {
+            return Err(Format);
+        };
+        if !empty.is_empty() {
+            return Err(Format);
+        }
+
+        let variant = match variant {
+            "argon2d" => Variant::Argon2d,
+            "argon2i" => Variant::Argon2i,
+            "argon2id" => Variant::Argon2id,
+            _ => return Err(Algorithm),
+        };
+
+        if version != format!("v={}", VERSION) {
+            return Err(Version);
+        }
+
+        let mut memory_cost = None;
+        let mut time_cost = None;
+        let mut parallelism = None;
+        let mut associated_data = None;
+
+        for parameter in parameters.split(',') {
+            let (name, value) = parameter.split_once('=').ok_or(Parameters)?;
+
+            let slot = match name {
+                "m" => &mut memory_cost,
+                "t" => &mut time_cost,
+                "p" => &mut parallelism,
+                "data" => {
+                    if associated_data.replace(base64_decode(value)?).is_some() {
+                        return Err(Parameters);
+                    }
+                    continue;
+                }
+                _ => return Err(Parameters),
+            };
+
+            // Plain decimal only, as the reference encoder writes it
+            if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) || value.starts_with('0') {
+                return Err(Parameters);
+            }
+            let value: u32 = value.parse().map_err(|_| Parameters)?;
+
+            if slot.replace(value).is_some() {
+                return Err(Parameters);
+            }
+        }
+
+        let (Some(memory_cost), Some(time_cost), Some(parallelism)) = (memory_cost, time_cost, parallelism)
+        else {
+            return Err(Parameters);
+        };
+
+        if !(1..=MAX_PARSED_TIME_COST).contains(&time_cost)
+            || !(1..=MAX_PARSED_PARALLELISM).contains(&parallelism)
+            || (memory_cost as u64) < 8 * parallelism as u64
+            || memory_cost > MAX_PARSED_MEMORY_COST
+        {
+            return Err(Parameters);
+        }
+
+        let salt = base64_decode(salt)?;
+        let hash = base64_decode(hash)?;
+        if salt.len() < MIN_SALT_LENGTH || hash.len() < MIN_OUTPUT_LENGTH || hash.len() > u32::MAX as usize {
+            return Err(Length);
+        }
+
+        // All within range, having been checked above
+        let params = Params::new(variant)
+            .memory_cost(memory_cost)
+            .time_cost(time_cost)
+            .and_then(|params| params.parallelism(parallelism))
+            .and_then(|params| params.output_length(hash.len()))
+            .map_err(|_| Parameters)?
+            .associated_data(&associated_data.unwrap_or_default());
+
+        Ok(PasswordHash { params, salt, hash })
+    }
+}
+
+/// An Argon2 parameter, or a salt, outside the range RFC 9106 allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidParameter {
+    /// A time cost of zero.
+    TimeCost,
+    /// Zero lanes, or more than 2^24 - 1.
+    Parallelism(u32),
+    /// Zero threads.
+    Threads,
+    /// Shorter than 4 bytes, or 2^32 bytes or longer.
+    OutputLength(usize),
+    /// Shorter than 8 bytes.
+    SaltLength(usize),
+    /// Less than 8 KiB per lane.
+    MemoryCost(u32),
+}
+
+impl fmt::Display for InvalidParameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidParameter::TimeCost => write!(f, "Argon2 time cost must be at least 1"),
+            InvalidParameter::Parallelism(n) => write!(f, "Argon2 parallelism of {} is out of range", n),
+            InvalidParameter::Threads => write!(f, "Argon2 must have at least 1 thread"),
+            InvalidParameter::OutputLength(n) => write!(f, "Argon2 output length of {} bytes is out of range", n),
+            InvalidParameter::SaltLength(n) => write!(f, "Argon2 salt of {} bytes is shorter than 8", n),
+            InvalidParameter::MemoryCost(n) => write!(f, "Argon2 memory cost of {} KiB is under 8 per lane", n),
+        }
+    }
+}
+
+impl Error for InvalidParameter {}
+
+/// Why a string couldn't be parsed as an Argon2 PHC string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsePasswordHashError {
+    /// The string isn't `$variant$v=...$params$salt$hash`.
+    Format,
+    /// The algorithm isn't one of the Argon2 variants.
+    Algorithm,
+    /// The version isn't 19 (0x13), the one RFC 9106 specifies.
+    Version,
+    /// A parameter is missing, repeated, unknown or out of range, including
+    /// above [`MAX_PARSED_MEMORY_COST`], [`MAX_PARSED_TIME_COST`] or
+    /// [`MAX_PARSED_PARALLELISM`].
+    Parameters,
+    /// The salt, hash or associated data isn't valid base64.
+    Encoding,
+    /// The salt or the hash is too short.
+    Length,
+}
+
+impl fmt::Display for ParsePasswordHashError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePasswordHashError::Format => write!(f, "not a PHC string"),
+            ParsePasswordHashError::Algorithm => write!(f, "not an Argon2 hash"),
+            ParsePasswordHashError::Version => write!(f, "unsupported Argon2 version"),
+            ParsePasswordHashError::Parameters => write!(f, "invalid Argon2 parameters"),
+            ParsePasswordHashError::Encoding => write!(f, "invalid base64"),
+            ParsePasswordHashError::Length => write!(f, "salt or hash too short"),
+        }
+    }
+}
+
+impl Error for ParsePasswordHashError {}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Standard base64 without the padding, as the PHC format has it
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let mut group = [0; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let bits = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+
+        for i in 0..chunk.len() + 1 {
+            encoded.push(BASE64[(bits >> (18 - 6 * i) & 63) as usize] as char);
+        }
+    }
+
+    encoded
+}
+
+fn base64_decode(encoded: &str) -> Result<Vec<u8>, ParsePasswordHashError> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+
+    for chunk in encoded.as_bytes().chunks(4) {
+        // A lone character can't make up a byte
+        if chunk.len() == 1 {
+            return Err(ParsePasswordHashError::Encoding);
+        }
+
+        let mut bits = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = BASE64
+                .iter()
+                .position(|&b| b == c)
+                .ok_or(ParsePasswordHashError::Encoding)?;
+            bits |= (value as u32) << (18 - 6 * i);
+        }
+
+        let length = chunk.len() - 1;
+        let group = bits.to_be_bytes();
+
+        // Bits past the last whole byte have to be zero, so that each
+        // value has just the one encoding
+        if group[1 + length..].iter().any(|&b| b != 0) {
+            return Err(ParsePasswordHashError::Encoding);
+        }
+
+        bytes.extend_from_slice(&group[1..1 + length]);
+    }
+
+    Ok(bytes)
+}
@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+use std::process;
+
+use hash_functions::blake3::Blake3;
+use hash_functions::stream::digest_file;
+
+fn main() {
+    let Some(file) = std::env::args_os().nth(1).map(PathBuf::from) else {
+        eprintln!("Usage: blake3 FILE");
+        process::exit(1);
+    };
+
+    match digest_file::<Blake3, _>(&file) {
+        Ok(digest) => println!("{}  {}", digest, file.display()),
+        Err(e) => {
+            eprintln!("{}: {}", file.display(), e);
+            process::exit(1);
+        }
+    }
+}
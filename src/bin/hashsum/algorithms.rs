@@ -0,0 +1,216 @@
+// Everything hashsum can compute, looked up by name.
+
+use hash_functions::blake2::{Blake2b, Blake2s};
+use hash_functions::blake3::Blake3;
+use hash_functions::ed2k::Ed2k;
+use hash_functions::keccak::Keccak256;
+use hash_functions::md4::Md4;
+use hash_functions::md5::Md5;
+use hash_functions::ripemd128::Ripemd128;
+use hash_functions::ripemd160::Ripemd160;
+use hash_functions::ripemd256::Ripemd256;
+use hash_functions::ripemd320::Ripemd320;
+use hash_functions::sha1::Sha1;
+use hash_functions::sha256::{Sha224, Sha256};
+use hash_functions::sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use hash_functions::sha512::{Sha384, Sha512, Sha512_224, Sha512_256};
+use hash_functions::Digest;
+
+pub struct Algorithm {
+    // What `-a` takes, and what the tool answers to as `<name>sum`
+    pub name: &'static str,
+    // What BSD-style `TAG (file) = digest` lines call it
+    pub tag: &'static str,
+    // Digest length in bytes
+    pub size: usize,
+    pub new: fn() -> Box<dyn Hasher>,
+}
+
+// What hashsum needs of a `Digest`, in a form that can be boxed, so that
+// every algorithm can be driven by the same code
+pub trait Hasher {
+    fn update(&mut self, input: &[u8]);
+    fn finalize(self: Box<Self>) -> Vec<u8>;
+}
+
+impl<D: Digest> Hasher for D {
+    fn update(&mut self, input: &[u8]) {
+        Digest::update(self, input)
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        Digest::finalize(*self).as_ref().to_vec()
+    }
+}
+
+fn new<D: Digest + 'static>() -> Box<dyn Hasher> {
+    Box::new(D::new())
+}
+
+pub const ALGORITHMS: &[Algorithm] = &[
+    Algorithm {
+        name: "md4",
+        tag: "MD4",
+        size: Md4::OUTPUT_SIZE,
+        new: new::<Md4>,
+    },
+    Algorithm {
+        name: "md5",
+        tag: "MD5",
+        size: Md5::OUTPUT_SIZE,
+        new: new::<Md5>,
+    },
+    Algorithm {
+        name: "sha1",
+        tag: "SHA1",
+        size: Sha1::OUTPUT_SIZE,
+        new: new::<Sha1>,
+    },
+    Algorithm {
+        name: "sha224",
+        tag: "SHA224",
+        size: Sha224::OUTPUT_SIZE,
+        new: new::<Sha224>,
+    },
+    Algorithm {
+        name: "sha256",
+        tag: "SHA256",
+        size: Sha256::OUTPUT_SIZE,
+        new: new::<Sha256>,
+    },
+    Algorithm {
+        name: "sha384",
+        tag: "SHA384",
+        size: Sha384::OUTPUT_SIZE,
+        new: new::<Sha384>,
+    },
+    Algorithm {
+        name: "sha512",
+        tag: "SHA512",
+        size: Sha512::OUTPUT_SIZE,
+        new: new::<Sha512>,
+    },
+    Algorithm {
+        name: "sha512-224",
+        tag: "SHA512/224",
+        size: Sha512_224::OUTPUT_SIZE,
+        new: new::<Sha512_224>,
+    },
+    Algorithm {
+        name: "sha512-256",
+        tag: "SHA512/256",
+        size: Sha512_256::OUTPUT_SIZE,
+        new: new::<Sha512_256>,
+    },
+    Algorithm {
+        name: "sha3-224",
+        tag: "SHA3-224",
+        size: Sha3_224::OUTPUT_SIZE,
+        new: new::<Sha3_224>,
+    },
+    Algorithm {
+        name: "sha3-256",
+        tag: "SHA3-256",
+        size: Sha3_256::OUTPUT_SIZE,
+        new: new::<Sha3_256>,
+    },
+    Algorithm {
+        name: "sha3-384",
+        tag: "SHA3-384",
+        size: Sha3_384::OUTPUT_SIZE,
+        new: new::<Sha3_384>,
+    },
+    Algorithm {
+        name: "sha3-512",
+        tag: "SHA3-512",
+        size: Sha3_512::OUTPUT_SIZE,
+        new: new::<Sha3_512>,
+    },
+    Algorithm {
+        name: "keccak256",
+        tag: "KECCAK-256",
+        size: Keccak256::OUTPUT_SIZE,
+        new: new::<Keccak256>,
+    },
+    Algorithm {
+        name: "blake2b",
+        tag: "BLAKE2b",
+        size: Blake2b::OUTPUT_SIZE,
+        new: new::<Blake2b>,
+    },
+    Algorithm {
+        name: "blake2s",
+        tag: "BLAKE2s",
+        size: Blake2s::OUTPUT_SIZE,
+        new: new::<Blake2s>,
+    },
+    Algorithm {
+        name: "blake3",
+        tag: "BLAKE3",
+        size: Blake3::OUTPUT_SIZE,
+        new: new::<Blake3>,
+    },
+    Algorithm {
+        name: "ripemd128",
+        tag: "RMD128",
+        size: Ripemd128::OUTPUT_SIZE,
+        new: new::<Ripemd128>,
+    },
+    Algorithm {
+        name: "ripemd160",
+        tag: "RMD160",
+        size: Ripemd160::OUTPUT_SIZE,
+        new: new::<Ripemd160>,
+    },
+    Algorithm {
+        name: "ripemd256",
+        tag: "RMD256",
+        size: Ripemd256::OUTPUT_SIZE,
+        new: new::<Ripemd256>,
+    },
+    Algorithm {
+        name: "ripemd320",
+        tag: "RMD320",
+        size: Ripemd320::OUTPUT_SIZE,
+        new: new::<Ripemd320>,
+    },
+    Algorithm {
+        name: "ed2k",
+        tag: "ED2K",
+        size: Ed2k::OUTPUT_SIZE,
+        new: new::<Ed2k>,
+    },
+];
+
+// Other names people know these by, mostly from the tools they replace
+const ALIASES: &[(&str, &str)] = &[
+    ("b2", "blake2b"),
+    ("b3", "blake3"),
+    ("blake2", "blake2b"),
+    ("keccak", "keccak256"),
+    ("rmd160", "ripemd160"),
+];
+
+pub fn find(name: &str) -> Option<&'static Algorithm> {
+    let name = name.to_ascii_lowercase();
+    let name = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map(|(_, target)| *target)
+        .unwrap_or(&name);
+
+    ALGORITHMS.iter().find(|algorithm| algorithm.name == name)
+}
+
+// When run through a link named e.g. `sha256sum` or `b2sum`, that settles the
+// algorithm.
+pub fn from_program_name(program: &str) -> Option<&'static Algorithm> {
+    let base = program.rsplit('/').next().unwrap_or(program);
+    let base = base.strip_suffix(".exe").unwrap_or(base);
+
+    find(base.strip_suffix("sum")?)
+}
+
+pub fn from_tag(tag: &str) -> Option<&'static Algorithm> {
+    ALGORITHMS.iter().find(|algorithm| algorithm.tag == tag)
+}
@@ -0,0 +1,106 @@
+// Just enough getopt to behave like the coreutils tools: long options with
+// their values (`--algorithm=sha1` or `--algorithm sha1`), bundled short
+// options (`-bz`, `-asha1`, `-a sha1`), and operands, with `--` ending the
+// options.
+//
+// Operands are usually file names, so they're kept as the OS gave them;
+// options and their values have to be UTF-8.
+
+use std::ffi::{OsStr, OsString};
+
+pub struct Spec {
+    // Every short option and the long option it stands for
+    pub short: &'static [(char, &'static str)],
+    // The long names of options that take a value
+    pub with_value: &'static [&'static str],
+}
+
+pub struct Args {
+    // (long name, value) in command line order, so callers only need to
+    // match on one form of each option
+    pub options: Vec<(String, Option<String>)>,
+    pub operands: Vec<OsString>,
+}
+
+// For operands that aren't file names
+pub fn to_str(operand: &OsStr) -> Result<&str, String> {
+    operand
+        .to_str()
+        .ok_or_else(|| format!("invalid argument '{}'", operand.to_string_lossy()))
+}
+
+pub fn parse(spec: &Spec, args: &[OsString]) -> Result<Args, String> {
+    let mut options = Vec::new();
+    let mut operands = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(os_arg) = args.next() {
+        if os_arg == "--" {
+            operands.extend(args.by_ref().cloned());
+            break;
+        }
+
+        let arg = match os_arg.to_str() {
+            Some(arg) => arg,
+            None if os_arg.as_encoded_bytes().starts_with(b"-") => {
+                return Err(format!("invalid option '{}'", os_arg.to_string_lossy()))
+            }
+            None => {
+                operands.push(os_arg.clone());
+                continue;
+            }
+        };
+
+        // The next argument, as an option's value, or `missing` if there
+        // isn't one
+        let mut value = |missing: String| match args.next() {
+            Some(value) => to_str(value).map(str::to_string),
+            None => Err(missing),
+        };
+
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline_value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+
+            let value = match (spec.with_value.contains(&name), inline_value) {
+                (true, Some(value)) => Some(value),
+                (true, None) => Some(value(format!("option '--{}' requires an argument", name))?),
+                (false, Some(_)) => {
+                    return Err(format!("option '--{}' doesn't allow an argument", name))
+                }
+                (false, None) => None,
+            };
+
+            options.push((name.to_string(), value));
+        } else if arg.len() > 1 && arg.starts_with('-') {
+            for (i, c) in arg[1..].char_indices() {
+                let name = match spec.short.iter().find(|(short, _)| *short == c) {
+                    Some((_, long)) => *long,
+                    None => return Err(format!("invalid option -- '{}'", c)),
+                };
+
+                if spec.with_value.contains(&name) {
+                    // The rest of the bundle, or failing that the next
+                    // argument, is the value
+                    let rest = &arg[1 + i + c.len_utf8()..];
+                    let value = if rest.is_empty() {
+                        value(format!("option requires an argument -- '{}'", c))?
+                    } else {
+                        rest.to_string()
+                    };
+
+                    options.push((name.to_string(), Some(value)));
+                    break;
+                }
+
+                options.push((name.to_string(), None));
+            }
+        } else {
+            operands.push(os_arg.clone());
+        }
+    }
+
+    Ok(Args { options, operands })
+}
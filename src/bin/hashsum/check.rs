@@ -0,0 +1,265 @@
+// `--check`: verify the files named in checksum lists, as written by hashsum
+// or the coreutils tools.  Each line is either GNU style, `<hex>  <name>` (or
+// `<hex> *<name>` for binary mode), or BSD style, `<TAG> (<name>) = <hex>`.
+// A leading backslash means the name has `\\` and `\n` escapes in it.
+//
+// Lists are read as bytes, not text, since the names in them are whatever
+// bytes the files are called.
+
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::io;
+use std::path::Path;
+use std::str::{self, FromStr};
+
+use hash_functions::VarOutput;
+
+use crate::algorithms::{self, Algorithm};
+use crate::format::escape;
+use crate::{describe, hash_input, read_input, write_out};
+
+pub struct Flags {
+    // Don't print OK for files that verify
+    pub quiet: bool,
+    // Don't print anything; the exit status says it all
+    pub status: bool,
+    // Fail if any line is improperly formatted
+    pub strict: bool,
+    // Complain about each improperly formatted line
+    pub warn: bool,
+    // Skip files that don't exist rather than failing them
+    pub ignore_missing: bool,
+}
+
+struct Entry {
+    algorithm: &'static Algorithm,
+    expected: VarOutput<64>,
+    name: OsString,
+}
+
+// GNU lines don't say which algorithm they're for, so they're checked with
+// `default`; BSD lines name their own.
+fn parse_line(line: &[u8], default: &'static Algorithm) -> Option<Entry> {
+    let (escaped, line) = match line.strip_prefix(b"\\") {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    let (algorithm, hex, name) = parse_bsd(line).or_else(|| parse_gnu(line, default))?;
+
+    let expected = VarOutput::from_str(str::from_utf8(hex).ok()?).ok()?;
+    if expected.len() != algorithm.size || name.is_empty() {
+        return None;
+    }
+
+    let name = if escaped {
+        unescape(name)?
+    } else {
+        name.to_vec()
+    };
+
+    Some(Entry {
+        algorithm,
+        expected,
+        name: file_name(name)?,
+    })
+}
+
+fn parse_bsd(line: &[u8]) -> Option<(&'static Algorithm, &[u8], &[u8])> {
+    let (tag, rest) = split_once(line, b" (")?;
+    let (name, hex) = rsplit_once(rest, b") = ")?;
+
+    Some((algorithms::from_tag(str::from_utf8(tag).ok()?)?, hex, name))
+}
+
+fn parse_gnu<'a>(
+    line: &'a [u8],
+    algorithm: &'static Algorithm,
+) -> Option<(&'static Algorithm, &'a [u8], &'a [u8])> {
+    let (hex, rest) = split_once(line, b" ")?;
+    let name = rest.strip_prefix(b" ").or_else(|| rest.strip_prefix(b"*"))?;
+
+    Some((algorithm, hex, name))
+}
+
+// The bytes either side of the first and last `separator` in `s`
+fn split_once<'a>(s: &'a [u8], separator: &[u8]) -> Option<(&'a [u8], &'a [u8])> {
+    let i = s.windows(separator.len()).position(|window| window == separator)?;
+    Some((&s[..i], &s[i + separator.len()..]))
+}
+
+fn rsplit_once<'a>(s: &'a [u8], separator: &[u8]) -> Option<(&'a [u8], &'a [u8])> {
+    let i = s.windows(separator.len()).rposition(|window| window == separator)?;
+    Some((&s[..i], &s[i + separator.len()..]))
+}
+
+fn unescape(name: &[u8]) -> Option<Vec<u8>> {
+    let mut result = Vec::with_capacity(name.len());
+
+    let mut bytes = name.iter();
+    while let Some(&b) = bytes.next() {
+        if b == b'\\' {
+            match bytes.next()? {
+                b'\\' => result.push(b'\\'),
+                b'n' => result.push(b'\n'),
+                b'r' => result.push(b'\r'),
+                _ => return None,
+            }
+        } else {
+            result.push(b);
+        }
+    }
+
+    Some(result)
+}
+
+// On Unix any bytes will do as a file name; elsewhere, they have to be UTF-8
+#[cfg(unix)]
+fn file_name(bytes: Vec<u8>) -> Option<OsString> {
+    use std::os::unix::ffi::OsStringExt;
+
+    Some(OsString::from_vec(bytes))
+}
+
+#[cfg(not(unix))]
+fn file_name(bytes: Vec<u8>) -> Option<OsString> {
+    String::from_utf8(bytes).ok().map(OsString::from)
+}
+
+fn plural(n: usize, one: &str, many: &str) -> String {
+    if n == 1 {
+        format!("{} {}", n, one)
+    } else {
+        format!("{} {}", n, many)
+    }
+}
+
+// Check every entry in `list`, reporting as coreutils does.  Returns whether
+// the whole list verified.
+pub fn check(
+    program: &str,
+    list: &OsStr,
+    algorithm: &'static Algorithm,
+    flags: &Flags,
+    buffer_size: usize,
+) -> bool {
+    let content = match read_input(list) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("{}: {}: {}", program, Path::new(list).display(), describe(&e));
+            return false;
+        }
+    };
+
+    let mut properly_formatted = 0;
+    let mut improperly_formatted = 0;
+    let mut unreadable = 0;
+    let mut mismatched = 0;
+    let mut verified = 0;
+
+    // Lines end with \n, or \r\n, and the last needn't end at all
+    let content = content.strip_suffix(b"\n").unwrap_or(&content);
+
+    for (i, line) in content.split(|&b| b == b'\n').enumerate() {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() || line.starts_with(b"#") {
+            continue;
+        }
+
+        let entry = match parse_line(line, algorithm) {
+            Some(entry) => entry,
+            None => {
+                improperly_formatted += 1;
+                if flags.warn {
+                    eprintln!(
+                        "{}: {}: {}: improperly formatted {} checksum line",
+                        program,
+                        Path::new(list).display(),
+                        i + 1,
+                        algorithm.tag
+                    );
+                }
+                continue;
+            }
+        };
+
+        properly_formatted += 1;
+
+        // Unlike checksum lines, reports only escape names that would
+        // otherwise break the line, as coreutils does
+        let file = entry.name.as_os_str();
+        let (prefix, name) = if file.as_encoded_bytes().iter().any(|b| b"\n\r".contains(b)) {
+            escape(file.as_encoded_bytes())
+        } else {
+            ("", Cow::Borrowed(file.as_encoded_bytes()))
+        };
+        let report = |result: &str| {
+            write_out(&[prefix.as_bytes(), &name, b": ", result.as_bytes(), b"\n"].concat());
+        };
+
+        match hash_input(entry.algorithm, file, buffer_size) {
+            Ok(digest) => {
+                verified += 1;
+
+                if digest == entry.expected.as_bytes() {
+                    if !flags.quiet && !flags.status {
+                        report("OK");
+                    }
+                } else {
+                    mismatched += 1;
+                    if !flags.status {
+                        report("FAILED");
+                    }
+                }
+            }
+            Err(e) if flags.ignore_missing && e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => {
+                unreadable += 1;
+                eprintln!("{}: {}: {}", program, Path::new(file).display(), describe(&e));
+                if !flags.status {
+                    report("FAILED open or read");
+                }
+            }
+        }
+    }
+
+    if properly_formatted == 0 {
+        eprintln!(
+            "{}: {}: no properly formatted checksum lines found",
+            program,
+            Path::new(list).display()
+        );
+        return false;
+    }
+
+    if !flags.status {
+        if improperly_formatted > 0 {
+            eprintln!(
+                "{}: WARNING: {} improperly formatted",
+                program,
+                plural(improperly_formatted, "line is", "lines are")
+            );
+        }
+        if unreadable > 0 {
+            eprintln!(
+                "{}: WARNING: {} not be read",
+                program,
+                plural(unreadable, "listed file could", "listed files could")
+            );
+        }
+        if mismatched > 0 {
+            eprintln!(
+                "{}: WARNING: {} NOT match",
+                program,
+                plural(mismatched, "computed checksum did", "computed checksums did")
+            );
+        }
+    }
+
+    if flags.ignore_missing && verified == 0 {
+        eprintln!("{}: {}: no file was verified", program, Path::new(list).display());
+        return false;
+    }
+
+    mismatched == 0 && unreadable == 0 && !(flags.strict && improperly_formatted > 0)
+}
@@ -0,0 +1,182 @@
+// `hashsum crypt`: make or check a crypt(3) string for a password, in the
+// manner of `mkpasswd`.  As with `hashsum pbkdf2`, the password comes from
+// standard input.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, Read};
+use std::process;
+
+use hash_functions::crypt::{self, md5_crypt, sha256_crypt, sha512_crypt, ALPHABET};
+
+use crate::args;
+
+const DEFAULT_METHOD: &str = "sha512";
+
+fn usage(program: &str) -> String {
+    format!(
+        "Usage: {program} crypt [OPTION]...
+Read a password from standard input and print its crypt(3) string, as
+stored in /etc/shadow.
+
+  -m, --method NAME      md5, sha256 or sha512 (default: {default})
+  -R, --rounds N         run N rounds, 1000 to 999999999 (sha256 and sha512
+                         only; default: 5000, left out of the string)
+  -S, --salt SALT        use SALT instead of a random one: up to 8
+                         characters for md5 and 16 for the others, from
+                         a-z, A-Z, 0-9, '.' and '/'
+  -c, --check CRYPT      check the password against CRYPT instead, and
+                         exit with status 1 if it doesn't match
+  -h, --help             display this help and exit
+
+The password is everything on standard input, less one trailing newline.
+",
+        program = program,
+        default = DEFAULT_METHOD,
+    )
+}
+
+const SPEC: args::Spec = args::Spec {
+    short: &[
+        ('c', "check"),
+        ('h', "help"),
+        ('m', "method"),
+        ('R', "rounds"),
+        ('S', "salt"),
+    ],
+    with_value: &["check", "method", "rounds", "salt"],
+};
+
+type Hash = fn(&[u8], &str, Option<u32>) -> String;
+
+struct Method {
+    max_salt: usize,
+    has_rounds: bool,
+    hash: Hash,
+}
+
+// md5crypt has no rounds to set
+fn md5_method(password: &[u8], salt: &str, _rounds: Option<u32>) -> String {
+    md5_crypt(password, salt)
+}
+
+fn find(name: &str) -> Option<Method> {
+    let (max_salt, has_rounds, hash): (_, _, Hash) =
+        match name.to_ascii_lowercase().replace('-', "").as_str() {
+            "md5" => (8, false, md5_method),
+            "sha256" => (16, true, sha256_crypt),
+            "sha512" => (16, true, sha512_crypt),
+            _ => return None,
+        };
+
+    Some(Method { max_salt, has_rounds, hash })
+}
+
+enum Action {
+    Make {
+        method: Method,
+        rounds: Option<u32>,
+        salt: Option<String>,
+    },
+    Check(String),
+}
+
+fn parse_args(program: &str, argv: &[OsString]) -> Result<Action, String> {
+    let args = args::parse(&SPEC, argv)?;
+
+    let mut method = DEFAULT_METHOD.to_string();
+    let mut rounds = None;
+    let mut salt = None;
+    let mut check = None;
+
+    for (name, value) in args.options {
+        match name.as_str() {
+            "help" => {
+                print!("{}", usage(program));
+                process::exit(0);
+            }
+            "check" => check = value,
+            "method" => method = value.unwrap(),
+            "rounds" => {
+                let value = value.unwrap();
+                rounds = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|n: &u32| (1000..=999_999_999).contains(n))
+                        .ok_or_else(|| format!("invalid number of rounds '{}'", value))?,
+                );
+            }
+            "salt" => salt = value,
+            _ => return Err(format!("unrecognized option '--{}'", name)),
+        }
+    }
+
+    if let Some(extra) = args.operands.first() {
+        return Err(format!("extra operand '{}'", extra.to_string_lossy()));
+    }
+
+    if let Some(shadow) = check {
+        return Ok(Action::Check(shadow));
+    }
+
+    let method = find(&method).ok_or_else(|| format!("unknown method '{}'", method))?;
+
+    if rounds.is_some() && !method.has_rounds {
+        return Err("md5 has a fixed number of rounds".to_string());
+    }
+
+    if let Some(salt) = &salt {
+        if !salt.bytes().all(|b| ALPHABET.contains(&b)) {
+            return Err(format!("invalid character in salt '{}'", salt));
+        }
+        if salt.len() > method.max_salt {
+            return Err(format!("salt '{}' is longer than {} characters", salt, method.max_salt));
+        }
+    }
+
+    Ok(Action::Make { method, rounds, salt })
+}
+
+// A salt of `length` characters from the kernel's random number generator
+fn random_salt(length: usize) -> io::Result<String> {
+    let mut bytes = vec![0; length];
+    File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+
+    // 256 is a multiple of 64, so every character is equally likely
+    Ok(bytes.iter().map(|&b| ALPHABET[(b % 64) as usize] as char).collect())
+}
+
+pub fn run(program: &str, argv: &[OsString]) -> Result<(), String> {
+    let action = parse_args(program, argv)?;
+
+    let mut password = Vec::new();
+    io::stdin()
+        .read_to_end(&mut password)
+        .map_err(|e| format!("reading the password: {}", e))?;
+
+    if password.last() == Some(&b'\n') {
+        password.pop();
+    }
+
+    match action {
+        Action::Make { method, rounds, salt } => {
+            let salt = match salt {
+                Some(salt) => salt,
+                None => random_salt(method.max_salt).map_err(|e| format!("making a salt: {}", e))?,
+            };
+
+            println!("{}", (method.hash)(&password, &salt, rounds));
+        }
+        Action::Check(shadow) => match crypt::verify(&password, &shadow) {
+            Ok(true) => {}
+            Ok(false) => {
+                eprintln!("{} crypt: password doesn't match", program);
+                process::exit(1);
+            }
+            Err(e) => return Err(e.to_string()),
+        },
+    }
+
+    Ok(())
+}
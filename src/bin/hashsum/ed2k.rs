@@ -0,0 +1,118 @@
+// `hashsum ed2k`: print eDonkey2000 links for files, with their ed2k and
+// AICH hashes.  (`hashsum -a ed2k` prints the ed2k hash alone, like any
+// other.)
+
+use std::ffi::{OsStr, OsString};
+use std::fs::File;
+use std::path::Path;
+use std::process;
+
+use hash_functions::ed2k::{link, Aich, Convention, Ed2k};
+use hash_functions::stream::{read_chunks, DEFAULT_BUFFER_SIZE};
+
+use crate::args;
+
+fn usage(program: &str) -> String {
+    format!(
+        "Usage: {program} ed2k [OPTION]... FILE...
+Print an ed2k:// link for each FILE, with its size, ed2k hash and AICH
+root hash.
+
+  -o, --old              hash files that are a whole number of 9728000-byte
+                         parts as eDonkey2000 did, with an empty part on the
+                         end, rather than as eMule does
+      --no-aich          leave the AICH hash out of the links
+  -h, --help             display this help and exit
+",
+        program = program,
+    )
+}
+
+const SPEC: args::Spec = args::Spec {
+    short: &[('h', "help"), ('o', "old")],
+    with_value: &[],
+};
+
+struct Options {
+    convention: Convention,
+    aich: bool,
+    files: Vec<OsString>,
+}
+
+fn parse_args(program: &str, argv: &[OsString]) -> Result<Options, String> {
+    let args = args::parse(&SPEC, argv)?;
+
+    let mut convention = Convention::New;
+    let mut aich = true;
+
+    for (name, _) in args.options {
+        match name.as_str() {
+            "help" => {
+                print!("{}", usage(program));
+                process::exit(0);
+            }
+            "old" => convention = Convention::Old,
+            "no-aich" => aich = false,
+            _ => return Err(format!("unrecognized option '--{}'", name)),
+        }
+    }
+
+    if args.operands.is_empty() {
+        return Err("missing operand".to_string());
+    }
+    if args.operands.iter().any(|file| file == "-") {
+        return Err("standard input has no name to link to".to_string());
+    }
+
+    Ok(Options {
+        convention,
+        aich,
+        files: args.operands,
+    })
+}
+
+// Both hashes in one pass over `file`, the AICH one if asked for
+fn link_file(file: &OsStr, options: &Options) -> std::io::Result<String> {
+    let mut ed2k = Ed2k::new();
+    let mut aich = options.aich.then(Aich::new);
+    let mut size = 0;
+
+    read_chunks(File::open(Path::new(file))?, DEFAULT_BUFFER_SIZE, |chunk| {
+        ed2k.update(chunk);
+        if let Some(aich) = &mut aich {
+            aich.update(chunk);
+        }
+        size += chunk.len() as u64;
+    })?;
+
+    // Links are UTF-8, so a name that isn't can only go in as best it can
+    let name = Path::new(file).file_name().unwrap_or(file).to_string_lossy();
+
+    Ok(link(
+        &name,
+        size,
+        &ed2k.finalize_with(options.convention),
+        aich.map(Aich::finalize).as_ref(),
+    ))
+}
+
+pub fn run(program: &str, argv: &[OsString]) -> Result<(), String> {
+    let options = parse_args(program, argv)?;
+    let mut failed = false;
+
+    for file in &options.files {
+        match link_file(file, &options) {
+            Ok(link) => println!("{}", link),
+            Err(e) => {
+                eprintln!("{} ed2k: {}: {}", program, Path::new(file).display(), crate::describe(&e));
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+// How each checksum line is written: GNU style (`<hex>  <name>`, or
+// `<hex> *<name>` in binary mode) or BSD style (`<TAG> (<name>) = <hex>`),
+// ending in a newline, or a NUL with `-z`.
+
+use std::borrow::Cow;
+
+use crate::algorithms::Algorithm;
+
+pub enum Style {
+    Gnu,
+    Bsd,
+}
+
+pub struct Format {
+    pub style: Style,
+    // Marks names with `*` rather than a space in GNU style.  Only cosmetic
+    // here: files are always read as bytes.
+    pub binary: bool,
+    pub zero: bool,
+}
+
+impl Format {
+    // `name` is in bytes, as file names are: it needn't be UTF-8.
+    pub fn line(&self, algorithm: &Algorithm, digest: &str, name: &[u8]) -> Vec<u8> {
+        // With NUL terminators a newline in a name is harmless, so, as with
+        // coreutils, names go out as they are.
+        let (prefix, name) = if self.zero {
+            ("", Cow::Borrowed(name))
+        } else {
+            escape(name)
+        };
+
+        let terminator = if self.zero { b'\0' } else { b'\n' };
+
+        let mut line = prefix.as_bytes().to_vec();
+        match self.style {
+            Style::Gnu => {
+                line.extend(digest.as_bytes());
+                line.extend(if self.binary { b" *" } else { b"  " });
+                line.extend(name.as_ref());
+            }
+            Style::Bsd => {
+                line.extend(format!("{} (", algorithm.tag).as_bytes());
+                line.extend(name.as_ref());
+                line.extend(format!(") = {}", digest).as_bytes());
+            }
+        }
+        line.push(terminator);
+
+        line
+    }
+}
+
+// A name with a backslash or line break in it has those escaped, and the
+// line it's on starts with a backslash to say so, so that every entry stays
+// on one line.  Returns that prefix (if any) and the name to write.
+pub fn escape(name: &[u8]) -> (&'static str, Cow<'_, [u8]>) {
+    if !name.iter().any(|b| b"\\\n\r".contains(b)) {
+        return ("", Cow::Borrowed(name));
+    }
+
+    let mut escaped = Vec::with_capacity(name.len() + 2);
+    for &b in name {
+        match b {
+            b'\\' => escaped.extend(b"\\\\"),
+            b'\n' => escaped.extend(b"\\n"),
+            b'\r' => escaped.extend(b"\\r"),
+            _ => escaped.push(b),
+        }
+    }
+
+    ("\\", Cow::Owned(escaped))
+}
@@ -0,0 +1,334 @@
+// Any of the crate's hash functions from a single command, in the style of
+// the coreutils *sum tools.
+
+mod algorithms;
+mod args;
+mod check;
+mod crypt;
+mod ed2k;
+mod format;
+mod pbkdf2;
+mod rsync;
+mod speed;
+
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process;
+
+use hash_functions::stream::{read_chunks, DEFAULT_BUFFER_SIZE};
+
+use algorithms::{Algorithm, ALGORITHMS};
+use format::{Format, Style};
+
+const DEFAULT_ALGORITHM: &str = "sha256";
+
+fn usage(program: &str) -> String {
+    let names: Vec<&str> = ALGORITHMS.iter().map(|algorithm| algorithm.name).collect();
+
+    format!(
+        "Usage: {program} [OPTION]... [FILE]...
+  or:  {program} speed [OPTION]... [ALGORITHM]...
+  or:  {program} pbkdf2 [OPTION]... -i ITERATIONS SALT
+  or:  {program} crypt [OPTION]...
+  or:  {program} signature|delta|patch [OPTION]... FILE...
+  or:  {program} ed2k [OPTION]... FILE...
+Print or check checksums of each FILE.  With no FILE, or when FILE is -,
+read standard input.
+
+  -a, --algorithm NAME   hash with NAME (default: {default})
+  -b, --binary           mark files as read in binary mode ('*' before names)
+      --buffer-size SIZE read input SIZE bytes at a time (default: {buffer});
+                         K, M and G suffixes multiply by powers of 1024
+  -c, --check            read checksums from the FILEs and check them
+      --tag              create a BSD-style checksum
+  -t, --text             mark files as read in text mode (default)
+  -z, --zero             end each output line with NUL, not newline,
+                         and don't escape file names
+  -h, --help             display this help and exit
+
+The following five options are useful only when verifying checksums:
+      --ignore-missing   don't fail or report status for missing files
+      --quiet            don't print OK for each successfully verified file
+      --status           don't output anything, status code shows success
+      --strict           exit non-zero for improperly formatted checksum lines
+  -w, --warn             warn about improperly formatted checksum lines
+
+When checking, lines are either '<digest>  <file>' or BSD-style
+'<TAG> (<file>) = <digest>'.  BSD lines are checked with the algorithm they
+name, the rest with the one selected.
+
+Algorithms: {names}
+
+Run through a link named after a coreutils tool (sha256sum, md5sum, b2sum,
+...), the algorithm is chosen to match.
+
+'{program} speed' benchmarks the algorithms instead, '{program} pbkdf2'
+derives a key from a password, '{program} crypt' makes and checks
+/etc/shadow password hashes, '{program} signature', 'delta' and 'patch'
+send the changes between files as rsync does, and '{program} ed2k' prints
+eDonkey2000 links; see '{program} speed --help' and so on.  (To hash a
+file with one of those names, write it as './speed' and so on.)
+",
+        program = program,
+        default = DEFAULT_ALGORITHM,
+        buffer = DEFAULT_BUFFER_SIZE,
+        names = names.join(", "),
+    )
+}
+
+struct Options {
+    algorithm: &'static Algorithm,
+    files: Vec<OsString>,
+    check: bool,
+    flags: check::Flags,
+    format: Format,
+    buffer_size: usize,
+}
+
+const SPEC: args::Spec = args::Spec {
+    short: &[
+        ('a', "algorithm"),
+        ('b', "binary"),
+        ('c', "check"),
+        ('h', "help"),
+        ('t', "text"),
+        ('w', "warn"),
+        ('z', "zero"),
+    ],
+    with_value: &["algorithm", "buffer-size"],
+};
+
+fn parse_args(program: &str, argv: &[OsString]) -> Result<Options, String> {
+    let args = args::parse(&SPEC, argv)?;
+    let mut files = args.operands;
+
+    let mut algorithm = algorithms::from_program_name(program);
+    let mut check = false;
+    // Binary or text, if either was asked for; the last one given wins
+    let mut binary = None;
+    let mut tag = false;
+    let mut zero = false;
+    let mut buffer_size = DEFAULT_BUFFER_SIZE;
+    let mut flags = check::Flags {
+        quiet: false,
+        status: false,
+        strict: false,
+        warn: false,
+        ignore_missing: false,
+    };
+
+    for (name, value) in args.options {
+        match name.as_str() {
+            "help" => {
+                print!("{}", usage(program));
+                process::exit(0);
+            }
+            "algorithm" => {
+                let value = value.unwrap();
+                algorithm = Some(
+                    algorithms::find(&value)
+                        .ok_or_else(|| format!("unknown algorithm '{}'", value))?,
+                );
+            }
+            "buffer-size" => {
+                let value = value.unwrap();
+                buffer_size =
+                    parse_size(&value).ok_or_else(|| format!("invalid buffer size '{}'", value))?;
+            }
+            "binary" => binary = Some(true),
+            "text" => binary = Some(false),
+            "tag" => tag = true,
+            "zero" => zero = true,
+            "check" => check = true,
+            "quiet" => flags.quiet = true,
+            "status" => flags.status = true,
+            "strict" => flags.strict = true,
+            "warn" => flags.warn = true,
+            "ignore-missing" => flags.ignore_missing = true,
+            _ => return Err(format!("unrecognized option '--{}'", name)),
+        }
+    }
+
+    if check {
+        if binary.is_some() {
+            return Err(
+                "the --binary and --text options are meaningless when verifying checksums"
+                    .to_string(),
+            );
+        }
+        if tag {
+            return Err("the --tag option is meaningless when verifying checksums".to_string());
+        }
+    } else {
+        let only_when_checking = [
+            ("ignore-missing", flags.ignore_missing),
+            ("quiet", flags.quiet),
+            ("status", flags.status),
+            ("strict", flags.strict),
+            ("warn", flags.warn),
+        ];
+
+        if let Some((name, _)) = only_when_checking.iter().find(|(_, set)| *set) {
+            return Err(format!(
+                "the --{} option is meaningful only when verifying checksums",
+                name
+            ));
+        }
+    }
+
+    if tag && binary == Some(false) {
+        return Err("--tag does not support --text mode".to_string());
+    }
+
+    if files.is_empty() {
+        files.push(OsString::from("-"));
+    }
+
+    Ok(Options {
+        algorithm: algorithm.unwrap_or_else(|| algorithms::find(DEFAULT_ALGORITHM).unwrap()),
+        files,
+        check,
+        flags,
+        format: Format {
+            style: if tag { Style::Bsd } else { Style::Gnu },
+            binary: binary.unwrap_or(false),
+            zero,
+        },
+        buffer_size,
+    })
+}
+
+// A positive number of bytes, optionally followed by K, M or G
+fn parse_size(value: &str) -> Option<usize> {
+    let (digits, multiplier) = match value.as_bytes().last()? {
+        b'K' | b'k' => (&value[..value.len() - 1], 1 << 10),
+        b'M' => (&value[..value.len() - 1], 1 << 20),
+        b'G' => (&value[..value.len() - 1], 1 << 30),
+        _ => (value, 1),
+    };
+
+    let size: usize = digits.parse().ok()?;
+    size.checked_mul(multiplier).filter(|&size| size > 0)
+}
+
+// Only for checksum lists, which are small; what's being hashed goes through
+// `hash_input`.
+fn read_input(file: &OsStr) -> io::Result<Vec<u8>> {
+    if file == "-" {
+        let mut content = Vec::new();
+        io::stdin().read_to_end(&mut content)?;
+        Ok(content)
+    } else {
+        fs::read(Path::new(file))
+    }
+}
+
+// Hash `file`, or standard input for "-", a buffer at a time.
+fn hash_input(algorithm: &Algorithm, file: &OsStr, buffer_size: usize) -> io::Result<Vec<u8>> {
+    let mut hasher = (algorithm.new)();
+
+    if file == "-" {
+        read_chunks(io::stdin().lock(), buffer_size, |chunk| hasher.update(chunk))?;
+    } else {
+        read_chunks(File::open(Path::new(file))?, buffer_size, |chunk| hasher.update(chunk))?;
+    }
+
+    Ok(hasher.finalize())
+}
+
+// Output that may hold file names, which go out byte for byte whether or not
+// they're UTF-8
+fn write_out(bytes: &[u8]) {
+    if let Err(e) = io::stdout().write_all(bytes) {
+        eprintln!("write error: {}", describe(&e));
+        process::exit(1);
+    }
+}
+
+// io::Error's Display tacks "(os error 2)" onto the message, which coreutils
+// doesn't.
+fn describe(e: &io::Error) -> String {
+    let message = e.to_string();
+
+    match message.find(" (os error ") {
+        Some(i) => message[..i].to_string(),
+        None => message,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn usage_error(program: &str, message: &str) -> ! {
+    eprintln!("{}: {}", program, message);
+    eprintln!("Try '{} --help' for more information.", program);
+    process::exit(1);
+}
+
+// Each takes the arguments after its name
+type Subcommand = fn(&str, &[OsString]) -> Result<(), String>;
+
+fn main() {
+    let args: Vec<OsString> = std::env::args_os().collect();
+    let program = args.first().map_or("hashsum".into(), |program| program.to_string_lossy());
+    let program = program.as_ref();
+
+    let subcommand: Option<Subcommand> = match args.get(1).and_then(|arg| arg.to_str()) {
+        Some("speed") => Some(speed::run),
+        Some("pbkdf2") => Some(pbkdf2::run),
+        Some("crypt") => Some(crypt::run),
+        Some("signature") => Some(rsync::signature),
+        Some("delta") => Some(rsync::delta),
+        Some("patch") => Some(rsync::patch),
+        Some("ed2k") => Some(ed2k::run),
+        _ => None,
+    };
+
+    if let Some(run) = subcommand {
+        if let Err(message) = run(program, &args[2..]) {
+            usage_error(&format!("{} {}", program, args[1].to_string_lossy()), &message);
+        }
+        return;
+    }
+
+    let options = match parse_args(program, &args[1..]) {
+        Ok(options) => options,
+        Err(message) => usage_error(program, &message),
+    };
+
+    let mut failed = false;
+
+    for file in &options.files {
+        if options.check {
+            failed |= !check::check(
+                program,
+                file,
+                options.algorithm,
+                &options.flags,
+                options.buffer_size,
+            );
+            continue;
+        }
+
+        match hash_input(options.algorithm, file, options.buffer_size) {
+            Ok(digest) => {
+                write_out(&options.format.line(
+                    options.algorithm,
+                    &hex(&digest),
+                    file.as_encoded_bytes(),
+                ));
+            }
+            Err(e) => {
+                eprintln!("{}: {}: {}", program, Path::new(file).display(), describe(&e));
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
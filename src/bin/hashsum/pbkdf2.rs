@@ -0,0 +1,163 @@
+// `hashsum pbkdf2`: derive a key from a password with PBKDF2, printing it
+// in hex.  The password comes from standard input rather than the command
+// line, where other users could read it from the process list.
+
+use std::ffi::OsString;
+use std::io::{self, Read};
+
+use hash_functions::pbkdf2::pbkdf2;
+use hash_functions::sha1::Sha1;
+use hash_functions::sha256::Sha256;
+use hash_functions::sha512::Sha512;
+use hash_functions::Digest;
+
+use crate::args;
+
+const DEFAULT_ALGORITHM: &str = "sha256";
+
+fn usage(program: &str) -> String {
+    format!(
+        "Usage: {program} pbkdf2 [OPTION]... -i ITERATIONS SALT
+Derive a key from the password on standard input and SALT with PBKDF2
+(RFC 8018), and print it in hex.
+
+  -a, --algorithm NAME   use HMAC-NAME: sha1, sha256 or sha512
+                         (default: {default})
+  -i, --iterations N     run N iterations (required)
+  -l, --length BYTES     derive BYTES bytes (default: the hash length)
+  -x, --hex-salt         take SALT as hex rather than as text
+  -h, --help             display this help and exit
+
+The password is everything on standard input, less one trailing newline,
+so that 'echo secret | {program} pbkdf2 ...' derives from 'secret'.
+",
+        program = program,
+        default = DEFAULT_ALGORITHM,
+    )
+}
+
+const SPEC: args::Spec = args::Spec {
+    short: &[
+        ('a', "algorithm"),
+        ('h', "help"),
+        ('i', "iterations"),
+        ('l', "length"),
+        ('x', "hex-salt"),
+    ],
+    with_value: &["algorithm", "iterations", "length"],
+};
+
+// PBKDF2 needs the hash as a type, so the choice is made here instead of
+// through the boxed hashers the rest of hashsum uses.
+type Derive = fn(&[u8], &[u8], u32, &mut [u8]);
+
+fn find(name: &str) -> Option<(Derive, usize)> {
+    match name.to_ascii_lowercase().replace('-', "").as_str() {
+        "sha1" => Some((pbkdf2::<Sha1>, Sha1::OUTPUT_SIZE)),
+        "sha256" => Some((pbkdf2::<Sha256>, Sha256::OUTPUT_SIZE)),
+        "sha512" => Some((pbkdf2::<Sha512>, Sha512::OUTPUT_SIZE)),
+        _ => None,
+    }
+}
+
+struct Options {
+    derive: Derive,
+    iterations: u32,
+    length: usize,
+    salt: Vec<u8>,
+}
+
+fn parse_args(program: &str, argv: &[OsString]) -> Result<Options, String> {
+    let args = args::parse(&SPEC, argv)?;
+
+    let mut algorithm = DEFAULT_ALGORITHM.to_string();
+    let mut iterations = None;
+    let mut length = None;
+    let mut hex_salt = false;
+
+    for (name, value) in args.options {
+        match name.as_str() {
+            "help" => {
+                print!("{}", usage(program));
+                std::process::exit(0);
+            }
+            "algorithm" => algorithm = value.unwrap(),
+            "iterations" => {
+                let value = value.unwrap();
+                iterations = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|&n: &u32| n > 0)
+                        .ok_or_else(|| format!("invalid number of iterations '{}'", value))?,
+                );
+            }
+            "length" => {
+                let value = value.unwrap();
+                length = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|&n: &usize| n > 0)
+                        .ok_or_else(|| format!("invalid length '{}'", value))?,
+                );
+            }
+            "hex-salt" => hex_salt = true,
+            _ => return Err(format!("unrecognized option '--{}'", name)),
+        }
+    }
+
+    let (derive, hash_length) = find(&algorithm)
+        .ok_or_else(|| format!("PBKDF2 is not available with '{}'", algorithm))?;
+
+    let iterations = iterations.ok_or("missing --iterations")?;
+
+    let salt = match args.operands.as_slice() {
+        [salt] if hex_salt => {
+            let salt = args::to_str(salt)?;
+            unhex(salt).ok_or_else(|| format!("invalid hex salt '{}'", salt))?
+        }
+        // As given, whether or not it's UTF-8
+        [salt] => salt.as_encoded_bytes().to_vec(),
+        [] => return Err("missing salt".to_string()),
+        [_, extra, ..] => return Err(format!("extra operand '{}'", extra.to_string_lossy())),
+    };
+
+    Ok(Options {
+        derive,
+        iterations,
+        length: length.unwrap_or(hash_length),
+        salt,
+    })
+}
+
+fn unhex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+pub fn run(program: &str, argv: &[OsString]) -> Result<(), String> {
+    let options = parse_args(program, argv)?;
+
+    let mut password = Vec::new();
+    io::stdin()
+        .read_to_end(&mut password)
+        .map_err(|e| format!("reading the password: {}", e))?;
+
+    if password.last() == Some(&b'\n') {
+        password.pop();
+    }
+
+    let mut key = vec![0; options.length];
+    (options.derive)(&password, &options.salt, options.iterations, &mut key);
+
+    println!("{}", crate::hex(&key));
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+// `hashsum signature`, `hashsum delta` and `hashsum patch`: rsync's delta
+// algorithm between files, in the manner of librsync's `rdiff`.  The
+// signature and delta formats are described in hash_functions::rsync.
+
+use std::ffi::{OsStr, OsString};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::process;
+
+use hash_functions::rsync::{self, Signature, StrongHash};
+
+use crate::args;
+
+const DEFAULT_BLOCK_SIZE: u32 = 2048;
+
+fn signature_usage(program: &str) -> String {
+    format!(
+        "Usage: {program} signature [OPTION]... BASIS [SIGNATURE]
+Write the block checksums of BASIS to SIGNATURE, for '{program} delta' to
+compare a newer version of the file against.
+
+  -b, --block-size BYTES checksum blocks of BYTES bytes (default: {block_size})
+  -m, --strong NAME      confirm blocks with NAME: md4 or md5 (default: md5)
+  -h, --help             display this help and exit
+
+With no SIGNATURE, or when it is -, write standard output; when BASIS is
+-, read standard input.
+",
+        program = program,
+        block_size = DEFAULT_BLOCK_SIZE,
+    )
+}
+
+fn delta_usage(program: &str) -> String {
+    format!(
+        "Usage: {program} delta [OPTION]... SIGNATURE NEW [DELTA]
+Write to DELTA the changes that turn the file SIGNATURE was made from into
+NEW.
+
+  -h, --help             display this help and exit
+
+With no DELTA, or when it is -, write standard output; when SIGNATURE or
+NEW is -, read standard input.
+",
+        program = program,
+    )
+}
+
+fn patch_usage(program: &str) -> String {
+    format!(
+        "Usage: {program} patch [OPTION]... BASIS DELTA [NEW]
+Apply DELTA to BASIS, writing the result to NEW, and check it against the
+checksum in DELTA.
+
+  -h, --help             display this help and exit
+
+With no NEW, or when it is -, write standard output; when DELTA is -,
+read standard input.  BASIS has to be a file.
+",
+        program = program,
+    )
+}
+
+const SIGNATURE_SPEC: args::Spec = args::Spec {
+    short: &[('b', "block-size"), ('h', "help"), ('m', "strong")],
+    with_value: &["block-size", "strong"],
+};
+
+const SPEC: args::Spec = args::Spec {
+    short: &[('h', "help")],
+    with_value: &[],
+};
+
+// The input operands named in `inputs` and an optional output one, which
+// is "-" when left off
+fn files(operands: Vec<OsString>, inputs: &[&str]) -> Result<Vec<OsString>, String> {
+    let mut files = operands;
+
+    if files.len() < inputs.len() {
+        return Err(format!("missing {} operand", inputs[files.len()]));
+    }
+    if let Some(extra) = files.get(inputs.len() + 1) {
+        return Err(format!("extra operand '{}'", extra.to_string_lossy()));
+    }
+    if files.len() == inputs.len() {
+        files.push(OsString::from("-"));
+    }
+
+    Ok(files)
+}
+
+// For delta and patch, which take no options but --help
+fn parse_args(usage: String, argv: &[OsString], inputs: &[&str]) -> Result<Vec<OsString>, String> {
+    let args = args::parse(&SPEC, argv)?;
+
+    if let Some((name, _)) = args.options.first() {
+        if name != "help" {
+            return Err(format!("unrecognized option '--{}'", name));
+        }
+
+        print!("{}", usage);
+        process::exit(0);
+    }
+
+    files(args.operands, inputs)
+}
+
+fn open(file: &OsStr) -> io::Result<Box<dyn Read>> {
+    if file == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(Path::new(file))?)))
+    }
+}
+
+fn create(file: &OsStr) -> io::Result<Box<dyn Write>> {
+    if file == "-" {
+        Ok(Box::new(BufWriter::new(io::stdout().lock())))
+    } else {
+        Ok(Box::new(BufWriter::new(File::create(Path::new(file))?)))
+    }
+}
+
+// Report `e` after `context`, which is the command and perhaps a file, and
+// exit
+fn fail(context: &str, e: io::Error) -> ! {
+    eprintln!("{}: {}", context, crate::describe(&e));
+    process::exit(1);
+}
+
+// The context for a failure with `file`
+fn in_file(command: &str, file: &OsStr) -> String {
+    format!("{}: {}", command, Path::new(file).display())
+}
+
+pub fn signature(program: &str, argv: &[OsString]) -> Result<(), String> {
+    let args = args::parse(&SIGNATURE_SPEC, argv)?;
+
+    let mut block_size = DEFAULT_BLOCK_SIZE;
+    let mut strong_hash = StrongHash::Md5;
+
+    for (name, value) in args.options {
+        match name.as_str() {
+            "help" => {
+                print!("{}", signature_usage(program));
+                process::exit(0);
+            }
+            "block-size" => {
+                let value = value.unwrap();
+                block_size = value
+                    .parse()
+                    .ok()
+                    .filter(|&n: &u32| n > 0)
+                    .ok_or_else(|| format!("invalid block size '{}'", value))?;
+            }
+            "strong" => {
+                let value = value.unwrap();
+                strong_hash = match value.to_ascii_lowercase().as_str() {
+                    "md4" => StrongHash::Md4,
+                    "md5" => StrongHash::Md5,
+                    _ => return Err(format!("unknown strong hash '{}'", value)),
+                };
+            }
+            _ => return Err(format!("unrecognized option '--{}'", name)),
+        }
+    }
+
+    let files = files(args.operands, &["BASIS"])?;
+    let (basis, output) = (&files[0], &files[1]);
+    let command = format!("{} signature", program);
+
+    let signature = open(basis)
+        .and_then(|input| Signature::generate(input, block_size, strong_hash))
+        .unwrap_or_else(|e| fail(&in_file(&command, basis), e));
+
+    let mut out = create(output).unwrap_or_else(|e| fail(&in_file(&command, output), e));
+    signature
+        .write_to(&mut out)
+        .and_then(|_| out.flush())
+        .unwrap_or_else(|e| fail(&in_file(&command, output), e));
+
+    Ok(())
+}
+
+pub fn delta(program: &str, argv: &[OsString]) -> Result<(), String> {
+    let files = parse_args(delta_usage(program), argv, &["SIGNATURE", "NEW"])?;
+    let (signature_file, new, output) = (&files[0], &files[1], &files[2]);
+    let command = format!("{} delta", program);
+
+    if signature_file == "-" && new == "-" {
+        return Err("SIGNATURE and NEW can't both be standard input".to_string());
+    }
+
+    let signature = open(signature_file)
+        .and_then(Signature::read_from)
+        .unwrap_or_else(|e| fail(&in_file(&command, signature_file), e));
+
+    let input = open(new).unwrap_or_else(|e| fail(&in_file(&command, new), e));
+    let out = create(output).unwrap_or_else(|e| fail(&in_file(&command, output), e));
+
+    rsync::delta(&signature, input, out).unwrap_or_else(|e| fail(&command, e));
+
+    Ok(())
+}
+
+pub fn patch(program: &str, argv: &[OsString]) -> Result<(), String> {
+    let files = parse_args(patch_usage(program), argv, &["BASIS", "DELTA"])?;
+    let (basis, delta, output) = (&files[0], &files[1], &files[2]);
+    let command = format!("{} patch", program);
+
+    if basis == "-" {
+        return Err("BASIS can't be standard input".to_string());
+    }
+
+    let basis_file = File::open(Path::new(basis)).unwrap_or_else(|e| fail(&in_file(&command, basis), e));
+    let input = open(delta).unwrap_or_else(|e| fail(&in_file(&command, delta), e));
+    let out = create(output).unwrap_or_else(|e| fail(&in_file(&command, output), e));
+
+    rsync::patch(basis_file, input, out).unwrap_or_else(|e| fail(&command, e));
+
+    Ok(())
+}
@@ -0,0 +1,242 @@
+// `hashsum speed`: how fast each algorithm runs here, in the manner of
+// `openssl speed`.  Every algorithm hashes in-memory messages of each size
+// over and over for a fixed time; each message is a complete hash from
+// `new` to `finalize`, so the small sizes show the cost of padding and
+// setup as well as of compression.
+
+use std::ffi::OsString;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::algorithms::{self, Algorithm, ALGORITHMS};
+use crate::args;
+
+const SIZES: &[usize] = &[16, 64, 256, 1024, 8 * 1024, 16 * 1024, 1024 * 1024];
+
+// Enough for figures steady to a few percent, while all the algorithms
+// together still take only half a minute or so
+const DEFAULT_SECONDS: f64 = 0.1;
+
+fn usage(program: &str) -> String {
+    let sizes: Vec<String> = SIZES.iter().map(|&size| size_label(size)).collect();
+
+    format!(
+        "Usage: {program} speed [OPTION]... [ALGORITHM]...
+Measure how fast each ALGORITHM (by default, all of them) hashes messages
+of each of these sizes: {sizes}.
+
+  -s, --seconds SECS     run each size for SECS seconds (default: {seconds})
+      --cpu-mhz MHZ      clock speed for the cycles per byte estimates
+                         (default: as /proc/cpuinfo reports it)
+  -h, --help             display this help and exit
+
+Throughput is in megabytes (10^6 bytes) per second.  Cycles per byte are
+estimated from the clock speed alone, so turbo and frequency scaling make
+them approximate.
+",
+        program = program,
+        sizes = sizes.join(", "),
+        seconds = DEFAULT_SECONDS,
+    )
+}
+
+fn size_label(size: usize) -> String {
+    if size >= 1 << 20 {
+        format!("{} MiB", size >> 20)
+    } else if size >= 1 << 10 {
+        format!("{} KiB", size >> 10)
+    } else {
+        format!("{} B", size)
+    }
+}
+
+// A table cell for a rate of `bytes_per_second`, in MB/s
+fn rate_cell(bytes_per_second: f64) -> String {
+    format!("{:.1}", bytes_per_second / 1e6)
+}
+
+// A table cell for the cycles per byte that `bytes_per_second` comes to on
+// a `mhz` clock
+fn cycles_per_byte_cell(mhz: f64, bytes_per_second: f64) -> String {
+    format!("{:.2}", mhz * 1e6 / bytes_per_second)
+}
+
+const SPEC: args::Spec = args::Spec {
+    short: &[('h', "help"), ('s', "seconds")],
+    with_value: &["seconds", "cpu-mhz"],
+};
+
+struct Options {
+    algorithms: Vec<&'static Algorithm>,
+    run_time: Duration,
+    cpu_mhz: Option<f64>,
+}
+
+fn parse_args(program: &str, argv: &[OsString]) -> Result<Options, String> {
+    let args = args::parse(&SPEC, argv)?;
+
+    let mut run_time = Duration::from_secs_f64(DEFAULT_SECONDS);
+    let mut cpu_mhz = None;
+
+    for (name, value) in args.options {
+        match name.as_str() {
+            "help" => {
+                print!("{}", usage(program));
+                std::process::exit(0);
+            }
+            "seconds" => {
+                let value = value.unwrap();
+                run_time = value
+                    .parse()
+                    .ok()
+                    .filter(|&seconds: &f64| seconds > 0.0 && seconds.is_finite())
+                    .map(Duration::from_secs_f64)
+                    .ok_or_else(|| format!("invalid number of seconds '{}'", value))?;
+            }
+            "cpu-mhz" => {
+                let value = value.unwrap();
+                cpu_mhz = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|&mhz: &f64| mhz > 0.0 && mhz.is_finite())
+                        .ok_or_else(|| format!("invalid clock speed '{}'", value))?,
+                );
+            }
+            _ => return Err(format!("unrecognized option '--{}'", name)),
+        }
+    }
+
+    let algorithms = if args.operands.is_empty() {
+        ALGORITHMS.iter().collect()
+    } else {
+        args.operands
+            .iter()
+            .map(|name| {
+                let name = args::to_str(name)?;
+                algorithms::find(name).ok_or_else(|| format!("unknown algorithm '{}'", name))
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    Ok(Options {
+        algorithms,
+        run_time,
+        cpu_mhz: cpu_mhz.or_else(cpu_mhz_from_proc),
+    })
+}
+
+// Linux reports the current clock speed of each core; the first will do.
+fn cpu_mhz_from_proc() -> Option<f64> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim() == "cpu MHz")
+        .and_then(|(_, value)| value.trim().parse().ok())
+}
+
+// Hash `message` over and over for `run_time`, returning the bytes hashed
+// per second.
+fn measure(algorithm: &Algorithm, message: &[u8], run_time: Duration) -> f64 {
+    // Check the clock only every so often, so that it costs next to nothing
+    // even for the smallest messages
+    let batch = (64 * 1024 / message.len()).max(1);
+
+    let start = Instant::now();
+    let mut count = 0;
+
+    while start.elapsed() < run_time {
+        for _ in 0..batch {
+            let mut hasher = (algorithm.new)();
+            hasher.update(message);
+            std::hint::black_box(hasher.finalize());
+        }
+        count += batch;
+    }
+
+    (count * message.len()) as f64 / start.elapsed().as_secs_f64()
+}
+
+fn print_row(name: &str, cells: &[String]) {
+    print!("{:<12}", name);
+    for cell in cells {
+        print!("{:>10}", cell);
+    }
+    println!();
+}
+
+pub fn run(program: &str, argv: &[OsString]) -> Result<(), String> {
+    let options = parse_args(program, argv)?;
+
+    let message: Vec<u8> = (0..*SIZES.last().unwrap()).map(|i| i as u8).collect();
+    let header: Vec<String> = SIZES.iter().map(|&size| size_label(size)).collect();
+
+    println!("Throughput in MB/s, {} s per size", options.run_time.as_secs_f64());
+    print_row("algorithm", &header);
+
+    let mut rates = Vec::new();
+
+    for algorithm in &options.algorithms {
+        let row: Vec<f64> = SIZES
+            .iter()
+            .map(|&size| measure(algorithm, &message[..size], options.run_time))
+            .collect();
+
+        let cells: Vec<String> = row.iter().map(|&rate| rate_cell(rate)).collect();
+        print_row(algorithm.name, &cells);
+
+        rates.push(row);
+    }
+
+    println!();
+
+    match options.cpu_mhz {
+        Some(mhz) => {
+            println!("Cycles per byte, estimated at {} MHz", mhz);
+            print_row("algorithm", &header);
+
+            for (algorithm, row) in options.algorithms.iter().zip(&rates) {
+                let cells: Vec<String> =
+                    row.iter().map(|&rate| cycles_per_byte_cell(mhz, rate)).collect();
+                print_row(algorithm.name, &cells);
+            }
+        }
+        None => println!("Clock speed unknown: give --cpu-mhz for cycles per byte"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_labels() {
+        let labels: Vec<String> = SIZES.iter().map(|&size| size_label(size)).collect();
+        assert_eq!(labels, ["16 B", "64 B", "256 B", "1 KiB", "8 KiB", "16 KiB", "1 MiB"]);
+
+        assert_eq!(size_label(0), "0 B");
+        assert_eq!(size_label(1023), "1023 B");
+        assert_eq!(size_label(1024 * 1024 - 1), "1023 KiB");
+        assert_eq!(size_label(64 << 20), "64 MiB");
+    }
+
+    #[test]
+    fn rate_cells() {
+        assert_eq!(rate_cell(0.0), "0.0");
+        assert_eq!(rate_cell(123_456_789.0), "123.5");
+        assert_eq!(rate_cell(40_000.0), "0.0");
+        assert_eq!(rate_cell(2.5e9), "2500.0");
+    }
+
+    #[test]
+    fn cycles_per_byte_cells() {
+        // 3 GHz hashing 1 GB/s is 3 cycles a byte
+        assert_eq!(cycles_per_byte_cell(3000.0, 1e9), "3.00");
+        assert_eq!(cycles_per_byte_cell(2400.0, 7e8), "3.43");
+        assert_eq!(cycles_per_byte_cell(1000.0, 1e6), "1000.00");
+    }
+}
@@ -0,0 +1,38 @@
+use std::fs::File;
+use std::path::PathBuf;
+use std::process;
+
+use hash_functions::sha3::Shake128;
+use hash_functions::stream::{read_chunks, DEFAULT_BUFFER_SIZE};
+
+// Output length in bytes defaults to 32, as with `openssl dgst -shake128`,
+// but can be given as a second argument.
+fn main() {
+    let mut args = std::env::args_os().skip(1);
+    let file = args.next().map(PathBuf::from);
+    let length = match args.next() {
+        Some(length) => length.to_str().and_then(|length| length.parse().ok()),
+        None => Some(32),
+    };
+
+    let (Some(file), Some(length)) = (file, length) else {
+        eprintln!("Usage: shake128 FILE [LENGTH]");
+        process::exit(1);
+    };
+
+    let mut hasher = Shake128::new();
+    let result = File::open(&file).and_then(|file| {
+        read_chunks(file, DEFAULT_BUFFER_SIZE, |chunk| hasher.update(chunk))
+    });
+
+    if let Err(e) = result {
+        eprintln!("{}: {}", file.display(), e);
+        process::exit(1);
+    }
+
+    let mut output = vec![0; length];
+    hasher.finalize_xof().read(&mut output);
+
+    let hex: String = output.iter().map(|b| format!("{:02x}", b)).collect();
+    println!("{}  {}", hex, file.display());
+}
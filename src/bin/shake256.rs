@@ -0,0 +1,38 @@
+use std::fs::File;
+use std::path::PathBuf;
+use std::process;
+
+use hash_functions::sha3::Shake256;
+use hash_functions::stream::{read_chunks, DEFAULT_BUFFER_SIZE};
+
+// Output length in bytes defaults to 64, as with `openssl dgst -shake256`,
+// but can be given as a second argument.
+fn main() {
+    let mut args = std::env::args_os().skip(1);
+    let file = args.next().map(PathBuf::from);
+    let length = match args.next() {
+        Some(length) => length.to_str().and_then(|length| length.parse().ok()),
+        None => Some(64),
+    };
+
+    let (Some(file), Some(length)) = (file, length) else {
+        eprintln!("Usage: shake256 FILE [LENGTH]");
+        process::exit(1);
+    };
+
+    let mut hasher = Shake256::new();
+    let result = File::open(&file).and_then(|file| {
+        read_chunks(file, DEFAULT_BUFFER_SIZE, |chunk| hasher.update(chunk))
+    });
+
+    if let Err(e) = result {
+        eprintln!("{}: {}", file.display(), e);
+        process::exit(1);
+    }
+
+    let mut output = vec![0; length];
+    hasher.finalize_xof().read(&mut output);
+
+    let hex: String = output.iter().map(|b| format!("{:02x}", b)).collect();
+    println!("{}  {}", hex, file.display());
+}
@@ -0,0 +1,608 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Digest, VarOutput};
+
+const IV: [u64; 8] = [
+    0x6A09E667F3BCC908,
+    0xBB67AE8584CAA73B,
+    0x3C6EF372FE94F82B,
+    0xA54FF53A5F1D36F1,
+    0x510E527FADE682D1,
+    0x9B05688C2B3E6C1F,
+    0x1F83D9ABFB41BD6B,
+    0x5BE0CD19137E2179,
+];
+
+// BLAKE2s borrows SHA-256's initial values
+const IV_S: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A,
+    0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0,  1,  2,  3,  4,  5,  6,  7,  8,  9,  10, 11, 12, 13, 14, 15],
+    [14, 10, 4,  8,  9,  15, 13, 6,  1,  12, 0,  2,  11, 7,  5,  3 ],
+    [11, 8,  12, 0,  5,  2,  15, 13, 10, 14, 3,  6,  7,  1,  9,  4 ],
+    [7,  9,  3,  1,  13, 12, 11, 14, 2,  6,  5,  10, 4,  0,  15, 8 ],
+    [9,  0,  5,  7,  2,  4,  10, 15, 14, 1,  11, 12, 6,  8,  3,  13],
+    [2,  12, 6,  10, 0,  11, 8,  3,  4,  13, 7,  5,  15, 14, 1,  9 ],
+    [12, 5,  1,  15, 14, 13, 4,  10, 0,  7,  6,  3,  9,  2,  8,  11],
+    [13, 11, 7,  14, 12, 1,  3,  9,  5,  0,  15, 4,  8,  6,  2,  10],
+    [6,  15, 14, 9,  11, 3,  0,  8,  12, 2,  13, 7,  1,  4,  10, 5 ],
+    [10, 2,  8,  4,  7,  6,  1,  5,  15, 11, 9,  14, 3,  12, 13, 0 ],
+];
+
+fn mix(v: &mut [u64; 16],
+       a: usize, b: usize, c: usize, d: usize,
+       x: u64, y: u64) {
+    mix_with(v, a, b, c, d, x, y, u64::wrapping_add);
+}
+
+// The G function with its addition of two state words left open: Argon2
+// runs it with a multiplication folded into each one, and no message words.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+pub(crate) fn mix_with(v: &mut [u64; 16],
+                       a: usize, b: usize, c: usize, d: usize,
+                       x: u64, y: u64,
+                       add: impl Fn(u64, u64) -> u64) {
+    v[a] = add(v[a], v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+
+    v[c] = add(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+
+    v[a] = add(v[a], v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+
+    v[c] = add(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+fn compress(h: &mut [u64; 8], chunk: &[u8; 128], t: u128, is_last_block: bool, is_last_node: bool) {
+    let mut v = [0; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV);
+
+    v[12] ^= ((t << 64) >> 64) as u64;
+    v[13] ^= (t >> 64) as u64;
+
+    if is_last_block {
+        v[14] = !v[14];
+    }
+
+    if is_last_node {
+        v[15] = !v[15];
+    }
+
+    let mut m = [0u64; 16];
+    for (word, eight_bytes) in m.iter_mut().zip(chunk.chunks_exact(8)) {
+        *word = u64::from_le_bytes(eight_bytes.try_into().unwrap());
+    }
+
+    for i in 0..12 {
+        let s = &SIGMA[i % 10];
+
+        mix(&mut v, 0, 4, 8,  12, m[s[0]], m[s[1]]);
+        mix(&mut v, 1, 5, 9,  13, m[s[2]], m[s[3]]);
+        mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+
+        mix(&mut v, 0, 5, 10, 15, m[s[8]],  m[s[9]]);
+        mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        mix(&mut v, 2, 7, 8,  13, m[s[12]], m[s[13]]);
+        mix(&mut v, 3, 4, 9,  14, m[s[14]], m[s[15]]);
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i];
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i + 8];
+    }
+}
+
+// BLAKE2s: the same structure over 32-bit words, with its own rotation
+// distances and two fewer rounds.  BLAKE3 uses this G function unchanged.
+pub(crate) fn mix_s(v: &mut [u32; 16],
+                    a: usize, b: usize, c: usize, d: usize,
+                    x: u32, y: u32) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(12);
+
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(8);
+
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(7);
+}
+
+// Indexing SIGMA by round number follows the specification's pseudocode
+#[allow(clippy::needless_range_loop)]
+fn compress_s(h: &mut [u32; 8], chunk: &[u8; 64], t: u64, is_last_block: bool) {
+    let mut v = [0; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV_S);
+
+    v[12] ^= t as u32;
+    v[13] ^= (t >> 32) as u32;
+
+    if is_last_block {
+        v[14] = !v[14];
+    }
+
+    let mut m = [0u32; 16];
+    for (word, four_bytes) in m.iter_mut().zip(chunk.chunks_exact(4)) {
+        *word = u32::from_le_bytes(four_bytes.try_into().unwrap());
+    }
+
+    for i in 0..10 {
+        let s = &SIGMA[i];
+
+        mix_s(&mut v, 0, 4, 8,  12, m[s[0]], m[s[1]]);
+        mix_s(&mut v, 1, 5, 9,  13, m[s[2]], m[s[3]]);
+        mix_s(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        mix_s(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+
+        mix_s(&mut v, 0, 5, 10, 15, m[s[8]],  m[s[9]]);
+        mix_s(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        mix_s(&mut v, 2, 7, 8,  13, m[s[12]], m[s[13]]);
+        mix_s(&mut v, 3, 4, 9,  14, m[s[14]], m[s[15]]);
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// A BLAKE2 parameter outside the range the specification allows, with the
+/// length (in bytes) that was asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidParameter {
+    /// Zero, or longer than the chaining value.
+    DigestLength(usize),
+    /// Longer than the chaining value.
+    KeyLength(usize),
+    /// Longer than 16 bytes.
+    SaltLength(usize),
+    /// Longer than 16 bytes.
+    PersonalLength(usize),
+    /// A maximum depth of zero.
+    MaxDepth,
+    /// Longer than 64 bytes.
+    InnerHashLength(usize),
+}
+
+impl fmt::Display for InvalidParameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidParameter::DigestLength(n) => write!(f, "BLAKE2 digest length of {} bytes is out of range", n),
+            InvalidParameter::KeyLength(n) => write!(f, "BLAKE2 key of {} bytes is too long", n),
+            InvalidParameter::SaltLength(n) => write!(f, "BLAKE2 salt of {} bytes is longer than 16", n),
+            InvalidParameter::PersonalLength(n) => write!(f, "BLAKE2 personalization of {} bytes is longer than 16", n),
+            InvalidParameter::MaxDepth => write!(f, "BLAKE2 maximum depth must be at least 1"),
+            InvalidParameter::InnerHashLength(n) => write!(f, "BLAKE2 inner hash length of {} bytes is longer than 64", n),
+        }
+    }
+}
+
+impl Error for InvalidParameter {}
+
+// Both variants cap the digest and the key at the size of their chaining
+// value: 64 bytes for BLAKE2b, 32 for BLAKE2s.
+fn check_digest_length(length: usize, max: usize) -> Result<(), InvalidParameter> {
+    if !(1..=max).contains(&length) {
+        return Err(InvalidParameter::DigestLength(length));
+    }
+
+    Ok(())
+}
+
+fn check_key_length(length: usize, max: usize) -> Result<(), InvalidParameter> {
+    if length > max {
+        return Err(InvalidParameter::KeyLength(length));
+    }
+
+    Ok(())
+}
+
+/// BLAKE2b's parameter block, for everything beyond the plain hash.
+///
+/// The defaults (64-byte digest, no key, sequential mode) give the same hash
+/// as `b2sum`.  Each setter returns the updated parameters, so they chain;
+/// those that can be given a value out of range return it in a `Result`:
+///
+/// ```
+/// use hash_functions::blake2::Params;
+///
+/// let digest = Params::new()
+///     .digest_length(32)?
+///     .personal(b"my-app v1")?
+///     .hash(b"hello");
+/// # assert_eq!(digest.len(), 32);
+/// # Ok::<(), hash_functions::blake2::InvalidParameter>(())
+/// ```
+///
+/// The tree parameters (fanout through last node) only matter to callers
+/// assembling a tree hash themselves: this just puts them in the parameter
+/// block and sets the last-node flag when asked.
+#[derive(Clone)]
+pub struct Params {
+    digest_length: u8,
+    key: [u8; 64],
+    key_length: u8,
+    salt: [u8; 16],
+    personal: [u8; 16],
+    fanout: u8,
+    max_depth: u8,
+    max_leaf_length: u32,
+    node_offset: u64,
+    node_depth: u8,
+    inner_hash_length: u8,
+    last_node: bool,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params {
+            digest_length: 64,
+            key: [0; 64],
+            key_length: 0,
+            salt: [0; 16],
+            personal: [0; 16],
+            fanout: 1,
+            max_depth: 1,
+            max_leaf_length: 0,
+            node_offset: 0,
+            node_depth: 0,
+            inner_hash_length: 0,
+            last_node: false,
+        }
+    }
+
+    /// Between 1 and 64 bytes.
+    pub fn digest_length(mut self, length: usize) -> Result<Params, InvalidParameter> {
+        check_digest_length(length, 64)?;
+
+        self.digest_length = length as u8;
+        Ok(self)
+    }
+
+    /// Up to 64 bytes.  An empty key is the same as no key at all.
+    pub fn key(mut self, key: &[u8]) -> Result<Params, InvalidParameter> {
+        check_key_length(key.len(), 64)?;
+
+        self.key = [0; 64];
+        self.key[..key.len()].copy_from_slice(key);
+        self.key_length = key.len() as u8;
+        Ok(self)
+    }
+
+    /// Up to 16 bytes; anything shorter is padded with zeros.
+    pub fn salt(mut self, salt: &[u8]) -> Result<Params, InvalidParameter> {
+        if salt.len() > 16 {
+            return Err(InvalidParameter::SaltLength(salt.len()));
+        }
+
+        self.salt = [0; 16];
+        self.salt[..salt.len()].copy_from_slice(salt);
+        Ok(self)
+    }
+
+    /// Up to 16 bytes; anything shorter is padded with zeros.
+    pub fn personal(mut self, personal: &[u8]) -> Result<Params, InvalidParameter> {
+        if personal.len() > 16 {
+            return Err(InvalidParameter::PersonalLength(personal.len()));
+        }
+
+        self.personal = [0; 16];
+        self.personal[..personal.len()].copy_from_slice(personal);
+        Ok(self)
+    }
+
+    /// 0 means unlimited.
+    pub fn fanout(mut self, fanout: u8) -> Params {
+        self.fanout = fanout;
+        self
+    }
+
+    /// Between 1 and 255.
+    pub fn max_depth(mut self, depth: u8) -> Result<Params, InvalidParameter> {
+        if depth == 0 {
+            return Err(InvalidParameter::MaxDepth);
+        }
+
+        self.max_depth = depth;
+        Ok(self)
+    }
+
+    /// In bytes; 0 means unlimited.
+    pub fn max_leaf_length(mut self, length: u32) -> Params {
+        self.max_leaf_length = length;
+        self
+    }
+
+    pub fn node_offset(mut self, offset: u64) -> Params {
+        self.node_offset = offset;
+        self
+    }
+
+    /// 0 for leaves.
+    pub fn node_depth(mut self, depth: u8) -> Params {
+        self.node_depth = depth;
+        self
+    }
+
+    /// Up to 64 bytes.
+    pub fn inner_hash_length(mut self, length: usize) -> Result<Params, InvalidParameter> {
+        if length > 64 {
+            return Err(InvalidParameter::InnerHashLength(length));
+        }
+
+        self.inner_hash_length = length as u8;
+        Ok(self)
+    }
+
+    /// Whether this node is the last (rightmost) at its depth in the tree.
+    pub fn last_node(mut self, last_node: bool) -> Params {
+        self.last_node = last_node;
+        self
+    }
+
+    /// The 64-byte parameter block, as laid out in section 2.5 of the BLAKE2
+    /// specification.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut block = [0; 64];
+
+        block[0] = self.digest_length;
+        block[1] = self.key_length;
+        block[2] = self.fanout;
+        block[3] = self.max_depth;
+        block[4..8].copy_from_slice(&self.max_leaf_length.to_le_bytes());
+        block[8..16].copy_from_slice(&self.node_offset.to_le_bytes());
+        block[16] = self.node_depth;
+        block[17] = self.inner_hash_length;
+        // bytes 18..32 are reserved and stay zero
+        block[32..48].copy_from_slice(&self.salt);
+        block[48..64].copy_from_slice(&self.personal);
+
+        block
+    }
+
+    /// A hasher configured with these parameters.
+    pub fn to_state(&self) -> Blake2b {
+        let block = self.to_bytes();
+
+        let mut h = [0u64; 8];
+        for (i, word) in block.chunks(8).enumerate() {
+            h[i] = IV[i] ^ u64::from_le_bytes(word.try_into().unwrap());
+        }
+
+        let mut state = Blake2b {
+            h,
+            buffer: [0; 128],
+            pos: 0,
+            t: 0,
+            digest_length: self.digest_length as usize,
+            last_node: self.last_node,
+        };
+
+        // The key goes in as a block of its own, padded out with zeros
+        if self.key_length > 0 {
+            state.buffer[..64].copy_from_slice(&self.key);
+            state.pos = 128;
+        }
+
+        state
+    }
+
+    pub fn hash(&self, input: &[u8]) -> VarOutput<64> {
+        let mut state = self.to_state();
+        state.update(input);
+        state.finalize()
+    }
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params::new()
+    }
+}
+
+/// BLAKE2b, as specified in RFC 7693.
+///
+/// [`Blake2b::new`] gives the unkeyed 64-byte hash produced by `b2sum`; use
+/// [`Params`] for anything else.  Input can be fed in with any number of
+/// calls to [`Blake2b::update`] before [`Blake2b::finalize`].
+#[derive(Clone)]
+pub struct Blake2b {
+    h: [u64; 8],
+    buffer: [u8; 128],
+    pos: usize,
+    t: u128,
+    digest_length: usize,
+    last_node: bool,
+}
+
+impl Blake2b {
+    pub fn new() -> Blake2b {
+        Params::new().to_state()
+    }
+
+    pub fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // Only compress a full buffer once we know more input follows:
+            // the final block has to be flagged as such.
+            if self.pos == 128 {
+                self.t += 128;
+                compress(&mut self.h, &self.buffer, self.t, false, false);
+                self.pos = 0;
+            }
+
+            let n = (128 - self.pos).min(input.len());
+            self.buffer[self.pos..self.pos + n].copy_from_slice(&input[..n]);
+            self.pos += n;
+            input = &input[n..];
+        }
+    }
+
+    pub fn finalize(mut self) -> VarOutput<64> {
+        self.t += self.pos as u128;
+        self.buffer[self.pos..].fill(0);
+        compress(&mut self.h, &self.buffer, self.t, true, self.last_node);
+
+        let mut bytes = [0; 64];
+        for (chunk, word) in bytes.chunks_mut(8).zip(&self.h) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+
+        VarOutput::new(&bytes[..self.digest_length])
+    }
+}
+
+impl Default for Blake2b {
+    fn default() -> Blake2b {
+        Blake2b::new()
+    }
+}
+
+impl Digest for Blake2b {
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 128;
+    const NAME: &'static str = "BLAKE2b";
+
+    // Variable, so that a state from `Params` with a shorter `digest_length`
+    // finalizes like any other
+    type Output = VarOutput<64>;
+
+    fn new() -> Blake2b {
+        Blake2b::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Blake2b::update(self, input)
+    }
+
+    fn finalize(self) -> VarOutput<64> {
+        Blake2b::finalize(self)
+    }
+}
+
+pub fn blake2(input: &[u8], key: Option<&[u8]>, hashlen: usize) -> Result<VarOutput<64>, InvalidParameter> {
+    Ok(Params::new()
+        .digest_length(hashlen)?
+        .key(key.unwrap_or(&[]))?
+        .hash(input))
+}
+
+/// BLAKE2s, as specified in RFC 7693: the 32-bit variant, for smaller
+/// platforms.
+///
+/// [`Blake2s::new`] gives the unkeyed 32-byte hash; [`Blake2s::new_keyed`]
+/// takes a key of up to 32 bytes and a digest length between 1 and 32, and
+/// returns an error for anything else.
+#[derive(Clone)]
+pub struct Blake2s {
+    h: [u32; 8],
+    buffer: [u8; 64],
+    pos: usize,
+    t: u64,
+    digest_length: usize,
+}
+
+impl Blake2s {
+    pub fn new() -> Blake2s {
+        Blake2s::new_keyed(&[], 32).unwrap()
+    }
+
+    pub fn new_keyed(key: &[u8], hashlen: usize) -> Result<Blake2s, InvalidParameter> {
+        check_digest_length(hashlen, 32)?;
+        check_key_length(key.len(), 32)?;
+
+        let mut h = [0u32; 8];
+        h.copy_from_slice(&IV_S);
+        h[0] ^= 0x01010000u32 | (key.len() << 8) as u32 | hashlen as u32;
+
+        let mut state = Blake2s {
+            h,
+            buffer: [0; 64],
+            pos: 0,
+            t: 0,
+            digest_length: hashlen,
+        };
+
+        // As with BLAKE2b, the key is a zero-padded block of its own
+        if !key.is_empty() {
+            state.buffer[..key.len()].copy_from_slice(key);
+            state.pos = 64;
+        }
+
+        Ok(state)
+    }
+
+    pub fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            if self.pos == 64 {
+                self.t += 64;
+                compress_s(&mut self.h, &self.buffer, self.t, false);
+                self.pos = 0;
+            }
+
+            let n = (64 - self.pos).min(input.len());
+            self.buffer[self.pos..self.pos + n].copy_from_slice(&input[..n]);
+            self.pos += n;
+            input = &input[n..];
+        }
+    }
+
+    pub fn finalize(mut self) -> VarOutput<32> {
+        self.t += self.pos as u64;
+        self.buffer[self.pos..].fill(0);
+        compress_s(&mut self.h, &self.buffer, self.t, true);
+
+        let mut bytes = [0; 32];
+        for (chunk, word) in bytes.chunks_mut(4).zip(&self.h) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+
+        VarOutput::new(&bytes[..self.digest_length])
+    }
+}
+
+impl Default for Blake2s {
+    fn default() -> Blake2s {
+        Blake2s::new()
+    }
+}
+
+impl Digest for Blake2s {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "BLAKE2s";
+
+    // As for BLAKE2b, variable to cover states from `new_keyed`
+    type Output = VarOutput<32>;
+
+    fn new() -> Blake2s {
+        Blake2s::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Blake2s::update(self, input)
+    }
+
+    fn finalize(self) -> VarOutput<32> {
+        Blake2s::finalize(self)
+    }
+}
+
+pub fn blake2s(input: &[u8], key: Option<&[u8]>, hashlen: usize) -> Result<VarOutput<32>, InvalidParameter> {
+    let mut state = Blake2s::new_keyed(key.unwrap_or(&[]), hashlen)?;
+    state.update(input);
+    Ok(state.finalize())
+}
@@ -0,0 +1,430 @@
+//! BLAKE3, following the reference implementation in the BLAKE3 paper.
+//!
+//! Input is split into 1 KiB chunks, each hashed on its own with a BLAKE2s
+//! style compression function; the chunks' chaining values then form the
+//! leaves of a binary tree whose root gives the output.  Any amount of output
+//! can be read from the root, and the same machinery provides a keyed hash
+//! and a key derivation function through different flags.
+
+use crate::blake2::mix_s;
+use crate::{Digest, Output};
+
+const OUT_LEN: usize = 32;
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+// SHA-256's initial values again, as in BLAKE2s
+const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A,
+    0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+// Instead of BLAKE2's SIGMA table, the message words are shuffled by this
+// same permutation after every round.
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+fn round(v: &mut [u32; 16], m: &[u32; 16]) {
+    mix_s(v, 0, 4, 8,  12, m[0], m[1]);
+    mix_s(v, 1, 5, 9,  13, m[2], m[3]);
+    mix_s(v, 2, 6, 10, 14, m[4], m[5]);
+    mix_s(v, 3, 7, 11, 15, m[6], m[7]);
+
+    mix_s(v, 0, 5, 10, 15, m[8],  m[9]);
+    mix_s(v, 1, 6, 11, 12, m[10], m[11]);
+    mix_s(v, 2, 7, 8,  13, m[12], m[13]);
+    mix_s(v, 3, 4, 9,  14, m[14], m[15]);
+}
+
+fn compress(
+    chaining_value: &[u32; 8],
+    block_words: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut v = [
+        chaining_value[0], chaining_value[1], chaining_value[2], chaining_value[3],
+        chaining_value[4], chaining_value[5], chaining_value[6], chaining_value[7],
+        IV[0], IV[1], IV[2], IV[3],
+        counter as u32, (counter >> 32) as u32, block_len, flags,
+    ];
+
+    let mut m = *block_words;
+
+    for r in 0..7 {
+        round(&mut v, &m);
+
+        if r < 6 {
+            let mut permuted = [0; 16];
+            for i in 0..16 {
+                permuted[i] = m[MSG_PERMUTATION[i]];
+            }
+            m = permuted;
+        }
+    }
+
+    for i in 0..8 {
+        v[i] ^= v[i + 8];
+        v[i + 8] ^= chaining_value[i];
+    }
+
+    v
+}
+
+fn words_from_le_bytes<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    let mut words = [0; N];
+    for (word, four_bytes) in words.iter_mut().zip(bytes.chunks(4)) {
+        *word = u32::from_le_bytes(four_bytes.try_into().unwrap());
+    }
+
+    words
+}
+
+fn first_8_words(words: [u32; 16]) -> [u32; 8] {
+    words[..8].try_into().unwrap()
+}
+
+// Everything needed to produce either a chaining value or, for the root node,
+// the final output.  The root's last compression is held back until we know
+// the node really is the root.
+#[derive(Clone)]
+struct Node {
+    input_chaining_value: [u32; 8],
+    block_words: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Node {
+    fn chaining_value(&self) -> [u32; 8] {
+        first_8_words(compress(
+            &self.input_chaining_value,
+            &self.block_words,
+            self.counter,
+            self.block_len,
+            self.flags,
+        ))
+    }
+
+    // Block `block_counter` of the extended output: each 64-byte block is an
+    // independent compression of the root, differing only in the counter.
+    fn root_output_block(&self, block_counter: u64) -> [u8; BLOCK_LEN] {
+        let words = compress(
+            &self.input_chaining_value,
+            &self.block_words,
+            block_counter,
+            self.block_len,
+            self.flags | ROOT,
+        );
+
+        let mut result = [0; BLOCK_LEN];
+        for (bytes, word) in result.chunks_mut(4).zip(&words) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+
+        result
+    }
+}
+
+fn parent_node(left: [u32; 8], right: [u32; 8], key: &[u32; 8], flags: u32) -> Node {
+    let mut block_words = [0; 16];
+    block_words[..8].copy_from_slice(&left);
+    block_words[8..].copy_from_slice(&right);
+
+    Node {
+        input_chaining_value: *key,
+        block_words,
+        counter: 0,
+        block_len: BLOCK_LEN as u32,
+        flags: PARENT | flags,
+    }
+}
+
+#[derive(Clone)]
+struct ChunkState {
+    chaining_value: [u32; 8],
+    chunk_counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+    flags: u32,
+}
+
+impl ChunkState {
+    fn new(key: &[u32; 8], chunk_counter: u64, flags: u32) -> ChunkState {
+        ChunkState {
+            chaining_value: *key,
+            chunk_counter,
+            block: [0; BLOCK_LEN],
+            block_len: 0,
+            blocks_compressed: 0,
+            flags,
+        }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed + self.block_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 {
+            CHUNK_START
+        } else {
+            0
+        }
+    }
+
+    fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // As in BLAKE2, a full block is only compressed once more input
+            // arrives, since the last block of the chunk is flagged.
+            if self.block_len == BLOCK_LEN {
+                let block_words = words_from_le_bytes(&self.block);
+                self.chaining_value = first_8_words(compress(
+                    &self.chaining_value,
+                    &block_words,
+                    self.chunk_counter,
+                    BLOCK_LEN as u32,
+                    self.flags | self.start_flag(),
+                ));
+                self.blocks_compressed += 1;
+                self.block = [0; BLOCK_LEN];
+                self.block_len = 0;
+            }
+
+            let n = (BLOCK_LEN - self.block_len).min(input.len());
+            self.block[self.block_len..self.block_len + n].copy_from_slice(&input[..n]);
+            self.block_len += n;
+            input = &input[n..];
+        }
+    }
+
+    fn output(&self) -> Node {
+        Node {
+            input_chaining_value: self.chaining_value,
+            block_words: words_from_le_bytes(&self.block),
+            counter: self.chunk_counter,
+            block_len: self.block_len as u32,
+            flags: self.flags | self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+/// An incremental BLAKE3 hasher, in any of its three modes.
+///
+/// Memory use is fixed however much input goes through it: besides the
+/// current chunk, only the chaining values down the right edge of the tree
+/// are kept, one per level.
+#[derive(Clone)]
+pub struct Blake3 {
+    chunk_state: ChunkState,
+    key: [u32; 8],
+    // Room for a tree over 2^54 chunks, which is as much input as the 64-bit
+    // counter allows
+    cv_stack: [[u32; 8]; 54],
+    cv_stack_len: usize,
+    flags: u32,
+}
+
+impl Blake3 {
+    fn with_key_and_flags(key: [u32; 8], flags: u32) -> Blake3 {
+        Blake3 {
+            chunk_state: ChunkState::new(&key, 0, flags),
+            key,
+            cv_stack: [[0; 8]; 54],
+            cv_stack_len: 0,
+            flags,
+        }
+    }
+
+    /// The plain hash function.
+    pub fn new() -> Blake3 {
+        Blake3::with_key_and_flags(IV, 0)
+    }
+
+    /// A keyed hash, usable as a MAC.
+    pub fn new_keyed(key: &[u8; 32]) -> Blake3 {
+        Blake3::with_key_and_flags(words_from_le_bytes(key), KEYED_HASH)
+    }
+
+    /// Key derivation: `context` should be a hardcoded, globally unique string
+    /// describing the purpose of the key, and the key material is then fed in
+    /// through [`Blake3::update`].
+    pub fn new_derive_key(context: &str) -> Blake3 {
+        let mut context_hasher = Blake3::with_key_and_flags(IV, DERIVE_KEY_CONTEXT);
+        context_hasher.update(context.as_bytes());
+
+        let context_key = context_hasher.finalize();
+        Blake3::with_key_and_flags(words_from_le_bytes(context_key.as_bytes()), DERIVE_KEY_MATERIAL)
+    }
+
+    fn push_stack(&mut self, cv: [u32; 8]) {
+        self.cv_stack[self.cv_stack_len] = cv;
+        self.cv_stack_len += 1;
+    }
+
+    fn pop_stack(&mut self) -> [u32; 8] {
+        self.cv_stack_len -= 1;
+        self.cv_stack[self.cv_stack_len]
+    }
+
+    // Each completed subtree is merged with its left neighbour as soon as
+    // both halves are complete.  After `total_chunks` chunks, that's once for
+    // every trailing zero bit in the count.
+    fn add_chunk_chaining_value(&mut self, mut new_cv: [u32; 8], mut total_chunks: u64) {
+        while total_chunks & 1 == 0 {
+            new_cv = parent_node(self.pop_stack(), new_cv, &self.key, self.flags).chaining_value();
+            total_chunks >>= 1;
+        }
+
+        self.push_stack(new_cv);
+    }
+
+    pub fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // Only finish a full chunk once more input arrives: if it turns
+            // out to be the only chunk, it's the root.
+            if self.chunk_state.len() == CHUNK_LEN {
+                let chunk_cv = self.chunk_state.output().chaining_value();
+                let total_chunks = self.chunk_state.chunk_counter + 1;
+                self.add_chunk_chaining_value(chunk_cv, total_chunks);
+                self.chunk_state = ChunkState::new(&self.key, total_chunks, self.flags);
+            }
+
+            let n = (CHUNK_LEN - self.chunk_state.len()).min(input.len());
+            self.chunk_state.update(&input[..n]);
+            input = &input[n..];
+        }
+    }
+
+    fn root_node(&self) -> Node {
+        // Fold the right edge of the tree back up into the root
+        let mut node = self.chunk_state.output();
+        let mut remaining = self.cv_stack_len;
+
+        while remaining > 0 {
+            remaining -= 1;
+            node = parent_node(self.cv_stack[remaining], node.chaining_value(), &self.key, self.flags);
+        }
+
+        node
+    }
+
+    /// The standard 32-byte output.
+    pub fn finalize(self) -> Output<32> {
+        self.output()
+    }
+
+    /// A reader for output of any length, of which the standard output is the
+    /// first 32 bytes.
+    pub fn finalize_xof(self) -> OutputReader {
+        self.output_xof()
+    }
+
+    /// The standard output for the input so far, leaving the hasher to take
+    /// more: BLAKE3 never changes its state to finish, so there's no need to
+    /// clone it first.
+    pub fn output(&self) -> Output<32> {
+        let mut result = [0; OUT_LEN];
+        self.output_xof().read(&mut result);
+        Output::new(result)
+    }
+
+    /// Like [`Blake3::output`], for output of any length.
+    pub fn output_xof(&self) -> OutputReader {
+        OutputReader {
+            root: self.root_node(),
+            position: 0,
+        }
+    }
+}
+
+impl Default for Blake3 {
+    fn default() -> Blake3 {
+        Blake3::new()
+    }
+}
+
+impl Digest for Blake3 {
+    const OUTPUT_SIZE: usize = OUT_LEN;
+    const BLOCK_SIZE: usize = BLOCK_LEN;
+    const NAME: &'static str = "BLAKE3";
+
+    type Output = Output<32>;
+
+    fn new() -> Blake3 {
+        Blake3::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Blake3::update(self, input)
+    }
+
+    fn finalize(self) -> Output<32> {
+        Blake3::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<32> {
+        blake3(input)
+    }
+}
+
+/// Extended output from [`Blake3::finalize_xof`].  Successive reads carry on
+/// from where the last one stopped, and since each output block is computed
+/// independently, the reader can also be moved to any position.
+#[derive(Clone)]
+pub struct OutputReader {
+    root: Node,
+    position: u64,
+}
+
+impl OutputReader {
+    pub fn read(&mut self, mut output: &mut [u8]) {
+        while !output.is_empty() {
+            let block = self.root.root_output_block(self.position / BLOCK_LEN as u64);
+            let offset = (self.position % BLOCK_LEN as u64) as usize;
+
+            let n = (BLOCK_LEN - offset).min(output.len());
+            output[..n].copy_from_slice(&block[offset..offset + n]);
+            output = &mut output[n..];
+            self.position += n as u64;
+        }
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position;
+    }
+}
+
+pub fn blake3(input: &[u8]) -> Output<32> {
+    let mut hasher = Blake3::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+pub fn keyed_hash(key: &[u8; 32], input: &[u8]) -> Output<32> {
+    let mut hasher = Blake3::new_keyed(key);
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// A 32-byte key for the purpose described by `context`, derived from
+/// `key_material`.  See [`Blake3::new_derive_key`] for other lengths.
+pub fn derive_key(context: &str, key_material: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake3::new_derive_key(context);
+    hasher.update(key_material);
+    hasher.finalize().into_bytes()
+}
@@ -0,0 +1,62 @@
+// Buffering shared by the incremental hashers: input arrives in arbitrary
+// pieces, but compression functions only ever want whole blocks.
+
+#[derive(Clone)]
+pub(crate) struct BlockBuffer<const N: usize> {
+    block: [u8; N],
+    pos: usize,
+}
+
+impl<const N: usize> BlockBuffer<N> {
+    pub(crate) fn new() -> BlockBuffer<N> {
+        BlockBuffer {
+            block: [0; N],
+            pos: 0,
+        }
+    }
+
+    // Feed `input` through, calling `compress` for each block that fills up.
+    // Whatever is left over stays buffered until the next call.
+    pub(crate) fn update(&mut self, mut input: &[u8], mut compress: impl FnMut(&[u8; N])) {
+        if self.pos > 0 {
+            let n = (N - self.pos).min(input.len());
+            self.block[self.pos..self.pos + n].copy_from_slice(&input[..n]);
+            self.pos += n;
+            input = &input[n..];
+
+            if self.pos < N {
+                return;
+            }
+
+            compress(&self.block);
+            self.pos = 0;
+        }
+
+        let mut blocks = input.chunks_exact(N);
+        for block in &mut blocks {
+            compress(block.try_into().unwrap());
+        }
+
+        let rest = blocks.remainder();
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.pos = rest.len();
+    }
+
+    // Merkle–Damgård strengthening: append a single 1 bit, zeros, and finally
+    // the encoded message length so that it ends exactly on a block boundary.
+    pub(crate) fn md_pad(&mut self, length: &[u8], mut compress: impl FnMut(&[u8; N])) {
+        self.block[self.pos] = 0x80;
+        self.pos += 1;
+
+        if self.pos > N - length.len() {
+            self.block[self.pos..].fill(0);
+            compress(&self.block);
+            self.pos = 0;
+        }
+
+        self.block[self.pos..N - length.len()].fill(0);
+        self.block[N - length.len()..].copy_from_slice(length);
+        compress(&self.block);
+        self.pos = 0;
+    }
+}
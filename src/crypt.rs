@@ -0,0 +1,281 @@
+//! The crypt(3) password hashes found in `/etc/shadow`: Poul-Henning Kamp's
+//! MD5-based scheme (`$1$`) and Ulrich Drepper's SHA-256 and SHA-512 ones
+//! (`$5$` and `$6$`).
+//!
+//! [`crypt`] works as crypt(3) does, taking its settings from a prefix of
+//! the string, so the same call both makes new hashes and recomputes old
+//! ones:
+//!
+//! ```
+//! use hash_functions::crypt::{crypt, verify};
+//!
+//! let shadow = crypt(b"Hello world!", "$5$rounds=10000$saltstringsaltstring").unwrap();
+//! assert_eq!(
+//!     shadow,
+//!     "$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA"
+//! );
+//!
+//! assert_eq!(verify(b"Hello world!", &shadow), Ok(true));
+//! assert_eq!(verify(b"Hello world?", &shadow), Ok(false));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::md5::Md5;
+use crate::sha256::Sha256;
+use crate::sha512::Sha512;
+use crate::{ct_eq, Digest};
+
+/// The characters crypt strings are written in: not the usual base64
+/// alphabet, but one that starts with "./" and puts the digits before the
+/// letters.  Salts are normally drawn from it too.
+pub const ALPHABET: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const MD5_MAX_SALT: usize = 8;
+const SHA_MAX_SALT: usize = 16;
+
+const ROUNDS_DEFAULT: u32 = 5000;
+const ROUNDS_MIN: u32 = 1000;
+const ROUNDS_MAX: u32 = 999_999_999;
+
+// The order the digest bytes are encoded in, three at a time with the
+// first of each three in the high bits.  The scrambling is part of each
+// scheme.
+const MD5_ORDER: [usize; 16] = [0, 6, 12, 1, 7, 13, 2, 8, 14, 3, 9, 15, 4, 10, 5, 11];
+
+const SHA256_ORDER: [usize; 32] = [
+    0, 10, 20, 21, 1, 11, 12, 22, 2, 3, 13, 23, 24, 4, 14, 15,
+    25, 5, 6, 16, 26, 27, 7, 17, 18, 28, 8, 9, 19, 29, 31, 30,
+];
+
+const SHA512_ORDER: [usize; 64] = [
+    0, 21, 42, 22, 43, 1, 44, 2, 23, 3, 24, 45, 25, 46, 4, 47,
+    5, 26, 6, 27, 48, 28, 49, 7, 50, 8, 29, 9, 30, 51, 31, 52,
+    10, 53, 11, 32, 12, 33, 54, 34, 55, 13, 56, 14, 35, 15, 36, 57,
+    37, 58, 16, 59, 17, 38, 18, 39, 60, 40, 61, 19, 62, 20, 41, 63,
+];
+
+/// Hash `password` as crypt(3) would with `setting`, which is either the
+/// start of a crypt string (`$6$salt`, `$5$rounds=10000$salt`) or a whole
+/// one, whose hash is then ignored.
+///
+/// As in crypt(3), the salt ends at the next `$` and is cut down to 8
+/// characters for MD5 and 16 for SHA, and a `rounds=` count outside 1000 to
+/// 999,999,999 is brought within it.
+pub fn crypt(password: &[u8], setting: &str) -> Result<String, UnsupportedScheme> {
+    if let Some(rest) = setting.strip_prefix("$1$") {
+        return Ok(md5_crypt(password, rest));
+    }
+
+    let (prefix, rest) = setting.split_at_checked(3).ok_or(UnsupportedScheme)?;
+    let sha = match prefix {
+        "$5$" => sha256_crypt,
+        "$6$" => sha512_crypt,
+        _ => return Err(UnsupportedScheme),
+    };
+
+    // A rounds= that glibc can't read a count from is just part of the salt
+    let (rounds, salt) = match rest.strip_prefix("rounds=").and_then(read_rounds) {
+        Some((rounds, salt)) => (Some(rounds), salt),
+        None => (None, rest),
+    };
+
+    Ok(sha(password, salt, rounds))
+}
+
+// The count at the start of `s` and the salt after it, read as glibc does:
+// with strtoul, so allowing white space and a sign before the digits, and
+// taking no digits at all as 0, but only if a '$' comes next.  Counts too
+// big for a u32 come back as u32::MAX, which is clamped to ROUNDS_MAX
+// anyway, as are the huge numbers strtoul makes of negative ones.
+fn read_rounds(s: &str) -> Option<(u32, &str)> {
+    let unsigned = s.trim_start_matches([' ', '\t', '\n', '\x0b', '\x0c', '\r']);
+    let (negative, unsigned) = match unsigned.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, unsigned.strip_prefix('+').unwrap_or(unsigned)),
+    };
+
+    let end = unsigned.bytes().position(|b| !b.is_ascii_digit()).unwrap_or(unsigned.len());
+    let (digits, after) = unsigned.split_at(end);
+
+    // Without digits, strtoul reads nothing, not even the sign
+    if digits.is_empty() {
+        return Some((0, s.strip_prefix('$')?));
+    }
+
+    let count = match digits.parse::<u32>().unwrap_or(u32::MAX) {
+        0 => 0,
+        _ if negative => u32::MAX,
+        count => count,
+    };
+    Some((count, after.strip_prefix('$')?))
+}
+
+/// Check `password` against a crypt string, such as the second field of an
+/// `/etc/shadow` line, in constant time.
+///
+/// Anything but the three schemes here is an error, including the `!` and
+/// `*` that mark locked accounts.
+pub fn verify(password: &[u8], shadow: &str) -> Result<bool, UnsupportedScheme> {
+    Ok(ct_eq(crypt(password, shadow)?.as_bytes(), shadow.as_bytes()))
+}
+
+/// The MD5-based crypt of FreeBSD and early Linux: `$1$salt$hash`.  Only
+/// for checking old hashes; it runs a fixed 1000 rounds of MD5.
+pub fn md5_crypt(password: &[u8], salt: &str) -> String {
+    let salt = truncate_salt(salt, MD5_MAX_SALT);
+
+    let alternate = digest::<Md5>(&[password, salt.as_bytes(), password]);
+
+    let mut state = Md5::new();
+    state.update(password);
+    state.update(b"$1$");
+    state.update(salt.as_bytes());
+    state.update(&repeat(alternate.as_ref(), password.len()));
+
+    // A quirk of the original: for each bit of the length, a NUL for a one
+    // and the first byte of the password for a zero
+    let mut length = password.len();
+    while length > 0 {
+        state.update(if length & 1 == 1 { &[0] } else { &password[..1] });
+        length >>= 1;
+    }
+
+    let hash = stretch::<Md5>(state.finalize(), password, salt.as_bytes(), 1000);
+
+    format!("$1${}${}", salt, encode(hash.as_ref(), &MD5_ORDER))
+}
+
+/// The SHA-256 crypt: `$5$salt$hash`, or `$5$rounds=N$salt$hash` when
+/// `rounds` is given.  Without it, 5000 rounds are run.
+pub fn sha256_crypt(password: &[u8], salt: &str, rounds: Option<u32>) -> String {
+    sha_crypt::<Sha256>("$5$", &SHA256_ORDER, password, salt, rounds)
+}
+
+/// The SHA-512 crypt: `$6$salt$hash`, or `$6$rounds=N$salt$hash` when
+/// `rounds` is given.  Without it, 5000 rounds are run.  The default on
+/// most Linux systems.
+pub fn sha512_crypt(password: &[u8], salt: &str, rounds: Option<u32>) -> String {
+    sha_crypt::<Sha512>("$6$", &SHA512_ORDER, password, salt, rounds)
+}
+
+// Drepper's scheme, which is the same for both hashes apart from the byte
+// order of the encoding
+fn sha_crypt<D: Digest>(prefix: &str, order: &[usize], password: &[u8], salt: &str, rounds: Option<u32>) -> String {
+    let salt = truncate_salt(salt, SHA_MAX_SALT);
+    let count = rounds.map_or(ROUNDS_DEFAULT, |rounds| rounds.clamp(ROUNDS_MIN, ROUNDS_MAX));
+
+    let alternate = digest::<D>(&[password, salt.as_bytes(), password]);
+
+    let mut state = D::new();
+    state.update(password);
+    state.update(salt.as_bytes());
+    state.update(&repeat(alternate.as_ref(), password.len()));
+
+    // For each bit of the length, the alternate digest for a one and the
+    // password for a zero
+    let mut length = password.len();
+    while length > 0 {
+        state.update(if length & 1 == 1 { alternate.as_ref() } else { password });
+        length >>= 1;
+    }
+
+    let a = state.finalize();
+
+    // Stand-ins for the password and the salt, of the same lengths, which
+    // the rounds use instead of them
+    let p = digest::<D>(&vec![password; password.len()]);
+    let p = repeat(p.as_ref(), password.len());
+
+    let s = digest::<D>(&vec![salt.as_bytes(); 16 + a.as_ref()[0] as usize]);
+    let s = repeat(s.as_ref(), salt.len());
+
+    let hash = stretch::<D>(a, &p, &s, count);
+
+    match rounds {
+        Some(_) => format!("{}rounds={}${}${}", prefix, count, salt, encode(hash.as_ref(), order)),
+        None => format!("{}{}${}", prefix, salt, encode(hash.as_ref(), order)),
+    }
+}
+
+// The rounds that make both schemes slow: each hashes the previous digest
+// with the password and salt, in an order that cycles every 42 rounds.
+fn stretch<D: Digest>(mut digest: D::Output, password: &[u8], salt: &[u8], rounds: u32) -> D::Output {
+    for i in 0..rounds {
+        let mut state = D::new();
+
+        state.update(if i % 2 == 1 { password } else { digest.as_ref() });
+        if i % 3 != 0 {
+            state.update(salt);
+        }
+        if i % 7 != 0 {
+            state.update(password);
+        }
+        state.update(if i % 2 == 1 { digest.as_ref() } else { password });
+
+        digest = state.finalize();
+    }
+
+    digest
+}
+
+fn digest<D: Digest>(parts: &[&[u8]]) -> D::Output {
+    let mut state = D::new();
+    for part in parts {
+        state.update(part);
+    }
+    state.finalize()
+}
+
+// `bytes` over and over, cut off at `length`
+fn repeat(bytes: &[u8], length: usize) -> Vec<u8> {
+    bytes.iter().cycle().take(length).copied().collect()
+}
+
+// Up to the first '$', and at most `max` bytes.  crypt(3) counts bytes
+// too; the cut is moved back to the start of a character if it would
+// split one, which can't happen with the salt characters crypt generates.
+fn truncate_salt(salt: &str, max: usize) -> &str {
+    let salt = salt.split('$').next().unwrap();
+
+    let mut end = salt.len().min(max);
+    while !salt.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    &salt[..end]
+}
+
+// crypt's base64: each three bytes in `order` (the last group possibly
+// short) become a 24-bit number, written out six bits at a time starting
+// from the lowest
+fn encode(digest: &[u8], order: &[usize]) -> String {
+    let mut encoded = String::new();
+
+    for group in order.chunks(3) {
+        let mut bits = 0u32;
+        for &i in group {
+            bits = bits << 8 | digest[i] as u32;
+        }
+
+        for _ in 0..group.len() + 1 {
+            encoded.push(ALPHABET[(bits & 63) as usize] as char);
+            bits >>= 6;
+        }
+    }
+
+    encoded
+}
+
+/// A crypt string for some scheme other than `$1$`, `$5$` or `$6$`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedScheme;
+
+impl fmt::Display for UnsupportedScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not an MD5, SHA-256 or SHA-512 crypt string")
+    }
+}
+
+impl Error for UnsupportedScheme {}
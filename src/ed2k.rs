@@ -0,0 +1,300 @@
+//! The eDonkey2000 file hash, and eMule's AICH hash tree, which between
+//! them identify files in `ed2k://` links.
+//!
+//! The ed2k hash is MD4 of each 9,728,000-byte part, then MD4 again over
+//! those digests one after the other; a file of one part is just its MD4.
+//! AICH is a SHA-1 hash tree over 180 KiB blocks of each part, whose root
+//! goes in links alongside it.
+//!
+//! ```
+//! use hash_functions::ed2k::{link, Aich, Ed2k};
+//! use hash_functions::Digest;
+//!
+//! let contents = b"abc";
+//! assert_eq!(
+//!     link("notes.txt", 3, &Ed2k::digest(contents), Some(&Aich::digest(contents))),
+//!     "ed2k://|file|notes.txt|3|a448017aaf21d8525fc10ae87aa6729d|h=VGMT4NSHA2AWVOR6EVYXQUGCNSONBWE5|/"
+//! );
+//! ```
+
+use crate::md4::Md4;
+use crate::sha1::Sha1;
+use crate::{Digest, Output};
+
+/// The size of the parts files are hashed and shared in.
+pub const PART_SIZE: u64 = 9_728_000;
+
+/// The size of AICH's blocks, the leaves of its tree; each part is 52 of
+/// them and a 140 KiB block over.
+pub const AICH_BLOCK_SIZE: u64 = 184_320;
+
+/// The two ways of hashing a file that is an exact multiple of
+/// [`PART_SIZE`] long, which give different hashes for the same file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Convention {
+    /// eDonkey2000's ("red"): the file ends with an empty part, whose MD4
+    /// is included like any other's.
+    Old,
+    /// eMule's ("blue"), followed by everything since: there is no empty
+    /// part.  Files of exactly one part hash to its MD4.
+    New,
+}
+
+/// The ed2k hash.
+///
+/// [`Ed2k::finalize`] follows the new convention for files that are a
+/// whole number of parts long; [`Ed2k::finalize_with`] takes either.
+#[derive(Clone)]
+pub struct Ed2k {
+    // The part being hashed, and how far into it we are
+    part: Md4,
+    part_length: u64,
+    // The digests of the parts before it, how many there were, and the
+    // first of them alone, which is the answer if there's only one
+    parts: Md4,
+    count: u64,
+    first: Option<Output<16>>,
+}
+
+impl Ed2k {
+    pub fn new() -> Ed2k {
+        Ed2k {
+            part: Md4::new(),
+            part_length: 0,
+            parts: Md4::new(),
+            count: 0,
+            first: None,
+        }
+    }
+
+    pub fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            let n = input.len().min((PART_SIZE - self.part_length) as usize);
+            self.part.update(&input[..n]);
+            self.part_length += n as u64;
+            input = &input[n..];
+
+            if self.part_length == PART_SIZE {
+                let part = std::mem::take(&mut self.part).finalize();
+                self.parts.update(part.as_ref());
+                self.count += 1;
+                self.first.get_or_insert(part);
+                self.part_length = 0;
+            }
+        }
+    }
+
+    pub fn finalize(self) -> Output<16> {
+        self.finalize_with(Convention::New)
+    }
+
+    pub fn finalize_with(mut self, convention: Convention) -> Output<16> {
+        let Some(first) = self.first else {
+            // Less than a part, or nothing at all
+            return self.part.finalize();
+        };
+
+        if self.part_length == 0 && convention == Convention::New {
+            // A whole number of parts, with no empty one after
+            return if self.count == 1 { first } else { self.parts.finalize() };
+        }
+
+        self.parts.update(self.part.finalize().as_ref());
+        self.parts.finalize()
+    }
+}
+
+impl Default for Ed2k {
+    fn default() -> Ed2k {
+        Ed2k::new()
+    }
+}
+
+// The block is a whole part: MD4 compresses 64 bytes at a time, but it's
+// the parts that the hash is made of
+impl Digest for Ed2k {
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = PART_SIZE as usize;
+    const NAME: &'static str = "ED2K";
+
+    type Output = Output<16>;
+
+    fn new() -> Ed2k {
+        Ed2k::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Ed2k::update(self, input)
+    }
+
+    fn finalize(self) -> Output<16> {
+        Ed2k::finalize(self)
+    }
+}
+
+/// The ed2k hash of `input`, by the new convention.
+pub fn ed2k(input: &[u8]) -> Output<16> {
+    let mut hasher = Ed2k::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// The root of eMule's AICH ("Advanced Intelligent Corruption Handling")
+/// tree: SHA-1 of each block, paired up part by part and then across parts
+/// as eMule does.
+///
+/// The leaves are kept until the end, 20 bytes for every 180 KiB of input.
+#[derive(Clone)]
+pub struct Aich {
+    block: Sha1,
+    block_length: u64,
+    part_length: u64,
+    length: u64,
+    leaves: Vec<Output<20>>,
+}
+
+impl Aich {
+    pub fn new() -> Aich {
+        Aich {
+            block: Sha1::new(),
+            block_length: 0,
+            part_length: 0,
+            length: 0,
+            leaves: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, mut input: &[u8]) {
+        self.length += input.len() as u64;
+
+        while !input.is_empty() {
+            // A block ends at the end of a part, however short that leaves it
+            let room = (AICH_BLOCK_SIZE - self.block_length).min(PART_SIZE - self.part_length);
+            let n = input.len().min(room as usize);
+            self.block.update(&input[..n]);
+            self.block_length += n as u64;
+            self.part_length += n as u64;
+            input = &input[n..];
+
+            if n as u64 == room {
+                self.end_block();
+                if self.part_length == PART_SIZE {
+                    self.part_length = 0;
+                }
+            }
+        }
+    }
+
+    pub fn finalize(mut self) -> Output<20> {
+        if self.block_length > 0 || self.leaves.is_empty() {
+            self.end_block();
+        }
+
+        let mut leaves = self.leaves.iter();
+        let root = aich_node(&mut leaves, self.length, true);
+        debug_assert!(leaves.next().is_none());
+        root
+    }
+
+    fn end_block(&mut self) {
+        self.leaves.push(std::mem::take(&mut self.block).finalize());
+        self.block_length = 0;
+    }
+}
+
+impl Default for Aich {
+    fn default() -> Aich {
+        Aich::new()
+    }
+}
+
+// The block is a leaf of the tree, each hashed whole with SHA-1
+impl Digest for Aich {
+    const OUTPUT_SIZE: usize = 20;
+    const BLOCK_SIZE: usize = AICH_BLOCK_SIZE as usize;
+    const NAME: &'static str = "AICH";
+
+    type Output = Output<20>;
+
+    fn new() -> Aich {
+        Aich::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Aich::update(self, input)
+    }
+
+    fn finalize(self) -> Output<20> {
+        Aich::finalize(self)
+    }
+}
+
+// The hash of the subtree over the next `length` bytes, taking its leaves
+// from `leaves`.  Above a part, the tree splits between parts, and below,
+// between blocks; a left branch takes the odd unit out and a right one
+// leaves it to its sibling, which is how eMule balances it.
+fn aich_node(leaves: &mut std::slice::Iter<Output<20>>, length: u64, left: bool) -> Output<20> {
+    let unit = if length <= PART_SIZE { AICH_BLOCK_SIZE } else { PART_SIZE };
+    if length <= unit {
+        return *leaves.next().unwrap();
+    }
+
+    let units = length.div_ceil(unit);
+    let left_length = (units + left as u64) / 2 * unit;
+
+    let mut hasher = Sha1::new();
+    hasher.update(aich_node(leaves, left_length, true).as_ref());
+    hasher.update(aich_node(leaves, length - left_length, false).as_ref());
+    hasher.finalize()
+}
+
+/// An `ed2k://` file link, which eMule and friends can download from.
+/// `name` is percent-encoded as it goes in; `aich`, if given, is written
+/// in base32 as the `h=` field.
+pub fn link(name: &str, size: u64, hash: &Output<16>, aich: Option<&Output<20>>) -> String {
+    let mut link = format!("ed2k://|file|{}|{}|{}|", percent_encode(name), size, hash);
+    if let Some(aich) = aich {
+        link += &format!("h={}|", base32(aich.as_ref()));
+    }
+    link + "/"
+}
+
+// Everything but letters, digits and "-._~" as %XX, byte by byte
+fn percent_encode(s: &str) -> String {
+    let mut encoded = String::new();
+
+    for &b in s.as_bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            encoded.push(b as char);
+        } else {
+            encoded += &format!("%{:02X}", b);
+        }
+    }
+
+    encoded
+}
+
+// RFC 4648 base32, without padding; AICH roots come out at 32 characters
+// exactly anyway
+fn base32(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+    let mut encoded = String::new();
+    let mut bits = 0u32;
+    let mut count = 0;
+
+    for &b in bytes {
+        bits = bits << 8 | b as u32;
+        count += 8;
+        while count >= 5 {
+            count -= 5;
+            encoded.push(ALPHABET[(bits >> count & 31) as usize] as char);
+        }
+    }
+
+    if count > 0 {
+        encoded.push(ALPHABET[(bits << (5 - count) & 31) as usize] as char);
+    }
+
+    encoded
+}
@@ -0,0 +1,118 @@
+//! HKDF, the HMAC-based extract-and-expand key derivation function of
+//! RFC 5869, for SHA-256, SHA-512 or any other of the crate's hashes.
+//!
+//! ```
+//! use hash_functions::hkdf::Hkdf;
+//! use hash_functions::sha256::Sha256;
+//!
+//! let hkdf = Hkdf::<Sha256>::new(b"salt", b"input key material");
+//!
+//! let mut encryption_key = [0; 32];
+//! let mut mac_key = [0; 32];
+//! hkdf.expand(b"encryption", &mut encryption_key).unwrap();
+//! hkdf.expand(b"authentication", &mut mac_key).unwrap();
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::hmac::Hmac;
+use crate::Digest;
+
+/// HKDF-Extract: condense `ikm`, the input keying material, into a
+/// pseudorandom key of one hash length.
+///
+/// An empty `salt` is the same as the RFC's default of a hash length of
+/// zeros, as HMAC pads short keys with zeros anyway.
+pub fn extract<D: Digest + Clone>(salt: &[u8], ikm: &[u8]) -> D::Output {
+    let mut mac = Hmac::<D>::new(salt);
+    mac.update(ikm);
+    mac.finalize()
+}
+
+/// A pseudorandom key ready for HKDF-Expand.
+///
+/// The HMAC state keyed with it is worked out once, up front, so any number
+/// of keys can be expanded from it for different `info` without redoing
+/// that.
+#[derive(Clone)]
+pub struct Hkdf<D: Digest + Clone> {
+    prk: Hmac<D>,
+}
+
+impl<D: Digest + Clone> Hkdf<D> {
+    /// Extract a pseudorandom key from `ikm` and `salt`, as [`extract`].
+    pub fn new(salt: &[u8], ikm: &[u8]) -> Hkdf<D> {
+        Hkdf::from_prk(extract::<D>(salt, ikm).as_ref())
+    }
+
+    /// Skip extraction, for when `prk` is already uniformly random and at
+    /// least a hash length long.
+    pub fn from_prk(prk: &[u8]) -> Hkdf<D> {
+        Hkdf {
+            prk: Hmac::new(prk),
+        }
+    }
+
+    /// HKDF-Expand: fill `okm` with keying material bound to `info`.
+    ///
+    /// At most 255 hash lengths can be produced; asking for more is an
+    /// error and leaves `okm` untouched.
+    pub fn expand(&self, info: &[u8], okm: &mut [u8]) -> Result<(), InvalidLength> {
+        let max = 255 * D::OUTPUT_SIZE;
+        if okm.len() > max {
+            return Err(InvalidLength {
+                requested: okm.len(),
+                max,
+            });
+        }
+
+        // T(i) = HMAC(PRK, T(i - 1) | info | i), with T(0) empty
+        let mut previous: Option<D::Output> = None;
+
+        for (i, chunk) in okm.chunks_mut(D::OUTPUT_SIZE).enumerate() {
+            let mut mac = self.prk.clone();
+            if let Some(previous) = &previous {
+                mac.update(previous.as_ref());
+            }
+            mac.update(info);
+            mac.update(&[i as u8 + 1]);
+
+            let t = mac.finalize();
+            chunk.copy_from_slice(&t.as_ref()[..chunk.len()]);
+            previous = Some(t);
+        }
+
+        Ok(())
+    }
+}
+
+/// Extract and expand in one go: fill `okm` with keying material derived
+/// from `ikm`, `salt` and `info`.
+pub fn hkdf<D: Digest + Clone>(
+    salt: &[u8],
+    ikm: &[u8],
+    info: &[u8],
+    okm: &mut [u8],
+) -> Result<(), InvalidLength> {
+    Hkdf::<D>::new(salt, ikm).expand(info, okm)
+}
+
+/// More output than HKDF-Expand can produce: 255 times the hash length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidLength {
+    pub requested: usize,
+    pub max: usize,
+}
+
+impl fmt::Display for InvalidLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "HKDF can expand to at most {} bytes with this hash, not {}",
+            self.max, self.requested
+        )
+    }
+}
+
+impl Error for InvalidLength {}
@@ -0,0 +1,105 @@
+//! HMAC, as specified in RFC 2104, over any of the crate's hash functions.
+//!
+//! ```
+//! use hash_functions::hmac::Hmac;
+//! use hash_functions::sha256::Sha256;
+//!
+//! let mut mac = Hmac::<Sha256>::new(b"key");
+//! mac.update(b"The quick brown fox jumps over the lazy dog");
+//! let tag = mac.finalize();
+//!
+//! assert_eq!(
+//!     tag.to_string(),
+//!     "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+//! );
+//! ```
+
+use crate::{ct_eq, Digest};
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+/// HMAC over the hash function `D`.
+///
+/// Input can be fed in with any number of calls to [`Hmac::update`] before
+/// [`Hmac::finalize`] produces the tag.
+///
+/// Setting up for a key costs a compression apiece for the inner and outer
+/// pads.  To authenticate many messages under one key, set up once and
+/// clone the result for each message, which starts from those states
+/// rather than redoing them.
+#[derive(Clone)]
+pub struct Hmac<D: Digest + Clone> {
+    // Hashes of the key XORed with ipad and opad respectively, each waiting
+    // for what follows
+    inner: D,
+    outer: D,
+}
+
+impl<D: Digest + Clone> Hmac<D> {
+    /// Keys of any length are accepted; ones longer than the hash's block
+    /// size are hashed first, as RFC 2104 says.
+    pub fn new(key: &[u8]) -> Hmac<D> {
+        let mut block = vec![0; D::BLOCK_SIZE];
+
+        if key.len() > D::BLOCK_SIZE {
+            let digest = D::digest(key);
+            block[..D::OUTPUT_SIZE].copy_from_slice(digest.as_ref());
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = D::new();
+        let mut outer = D::new();
+
+        for b in block.iter_mut() {
+            *b ^= IPAD;
+        }
+        inner.update(&block);
+
+        for b in block.iter_mut() {
+            *b ^= IPAD ^ OPAD;
+        }
+        outer.update(&block);
+
+        Hmac { inner, outer }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.inner.update(input);
+    }
+
+    pub fn finalize(self) -> D::Output {
+        let mut outer = self.outer;
+        outer.update(self.inner.finalize().as_ref());
+        outer.finalize()
+    }
+
+    /// Check the message against a full-length `tag`, taking the same time
+    /// wherever the first difference falls.
+    pub fn verify(self, tag: &[u8]) -> bool {
+        ct_eq(self.finalize().as_ref(), tag)
+    }
+
+    /// Check the message against a tag truncated to its leftmost bytes.
+    ///
+    /// RFC 2104 recommends keeping at least half of the output, and no
+    /// fewer than 80 bits; shorter tags than that are rejected outright,
+    /// as are ones longer than the output.
+    pub fn verify_truncated(self, tag: &[u8]) -> bool {
+        let min = (D::OUTPUT_SIZE / 2).max(10).min(D::OUTPUT_SIZE);
+
+        if tag.len() < min || tag.len() > D::OUTPUT_SIZE {
+            return false;
+        }
+
+        ct_eq(&self.finalize().as_ref()[..tag.len()], tag)
+    }
+}
+
+/// HMAC of `message` under `key`, in one go.
+pub fn hmac<D: Digest + Clone>(key: &[u8], message: &[u8]) -> D::Output {
+    let mut mac = Hmac::<D>::new(key);
+    mac.update(message);
+    mac.finalize()
+}
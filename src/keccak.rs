@@ -0,0 +1,119 @@
+//! The original Keccak submission, as used by Ethereum.  The permutation and
+//! sponge are the same as SHA-3's; only the padding differs, as the
+//! domain-separation bits FIPS 202 added came after Ethereum settled on it.
+
+use crate::sha3::Sponge;
+use crate::{Digest, Output};
+
+// Plain pad10*1 with no suffix bits
+const KECCAK_DOMAIN: u8 = 0x01;
+
+/// Keccak-256, with the pre-standard padding.
+///
+/// Input can be fed in with any number of calls to [`Keccak256::update`]
+/// before [`Keccak256::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Keccak256(Sponge);
+
+impl Keccak256 {
+    pub fn new() -> Keccak256 {
+        Keccak256(Sponge::new(136, KECCAK_DOMAIN))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.absorb(input);
+    }
+
+    pub fn finalize(mut self) -> Output<32> {
+        let mut result = [0; 32];
+        self.0.pad();
+        self.0.squeeze(&mut result);
+        Output::new(result)
+    }
+}
+
+impl Default for Keccak256 {
+    fn default() -> Keccak256 {
+        Keccak256::new()
+    }
+}
+
+impl Digest for Keccak256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 136;
+    const NAME: &'static str = "Keccak-256";
+
+    type Output = Output<32>;
+
+    fn new() -> Keccak256 {
+        Keccak256::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Keccak256::update(self, input)
+    }
+
+    fn finalize(self) -> Output<32> {
+        Keccak256::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<32> {
+        keccak256(input)
+    }
+}
+
+pub fn keccak256(input: &[u8]) -> Output<32> {
+    let mut hasher = Keccak256::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// Render a 20-byte Ethereum address in EIP-55's mixed-case form: each hex
+/// letter is upper case if the matching nibble of the Keccak-256 of the
+/// lowercase address is 8 or more.
+pub fn checksum_address(address: &[u8; 20]) -> String {
+    let lower: String = address.iter().map(|b| format!("{:02x}", b)).collect();
+    let hash = keccak256(lower.as_bytes());
+
+    let mut result = String::from("0x");
+
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = (hash.as_bytes()[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+
+        if nibble >= 8 {
+            result.push(c.to_ascii_uppercase());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Parse a `0x`-prefixed hex address, accepting it if it's entirely lower
+/// case, entirely upper case (neither carries a checksum), or carries a
+/// correct EIP-55 checksum.  Returns `None` for anything else.
+pub fn parse_address(s: &str) -> Option<[u8; 20]> {
+    let hex = s.strip_prefix("0x")?;
+    let address = *hex.parse::<Output<20>>().ok()?.as_bytes();
+
+    let all_lower = !hex.chars().any(|c| c.is_ascii_uppercase());
+    let all_upper = !hex.chars().any(|c| c.is_ascii_lowercase());
+
+    if all_lower || all_upper || checksum_address(&address) == s {
+        Some(address)
+    } else {
+        None
+    }
+}
+
+/// The 4-byte selector that identifies a contract function in call data: the
+/// start of the Keccak-256 of its canonical signature, such as
+/// `transfer(address,uint256)`.
+pub fn function_selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+
+    let mut result = [0; 4];
+    result.copy_from_slice(&hash.as_bytes()[..4]);
+    result
+}
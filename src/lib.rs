@@ -0,0 +1,88 @@
+//! Standalone implementations of a handful of hash functions, transcribed
+//! from their published pseudocode.
+//!
+//! Each algorithm lives in its own module and exposes both a plain function
+//! (e.g. [`sha256::sha256`]) and a type implementing [`Digest`], so code can
+//! be written against any of them.
+//!
+//! Built on top of those, [`hmac`] does keyed message authentication with
+//! any of the hashes, and [`hkdf`] and [`pbkdf2`] derive keys with it.
+//! [`argon2`] is a memory-hard password hash on top of BLAKE2b, and
+//! [`crypt`] makes and checks the Unix `$1$`, `$5$` and `$6$` ones, and
+//! [`ntlm`] the Windows NT hash and NTLMv2 responses.  [`rsync`] uses MD4
+//! or MD5 to send just the changes between two versions of a file, and
+//! [`ed2k`] hashes files for eDonkey2000 links.
+
+mod buffer;
+mod output;
+
+pub mod argon2;
+pub mod blake2;
+pub mod blake3;
+pub mod crypt;
+pub mod ed2k;
+pub mod hkdf;
+pub mod hmac;
+pub mod keccak;
+pub mod md4;
+pub mod md5;
+pub mod ntlm;
+pub mod pbkdf2;
+pub mod ripemd128;
+pub mod ripemd160;
+pub mod ripemd256;
+pub mod ripemd320;
+pub mod rsync;
+pub mod sha1;
+pub mod sha256;
+pub mod sha3;
+pub mod sha512;
+pub mod stream;
+
+pub use crate::output::{ct_eq, Output, ParseOutputError, VarOutput};
+
+use std::fmt;
+
+/// A hash function, applied either to a byte string in one shot or
+/// incrementally to input that arrives in pieces.
+///
+/// ```
+/// use hash_functions::sha256::Sha256;
+/// use hash_functions::Digest;
+///
+/// let mut hasher = Sha256::new();
+/// hasher.update(b"hello ");
+/// hasher.update(b"world");
+/// assert_eq!(hasher.finalize(), Sha256::digest(b"hello world"));
+/// ```
+pub trait Digest: Sized {
+    /// The digest itself, e.g. [`Output<32>`] for SHA-256.
+    type Output: AsRef<[u8]> + Clone + fmt::Display + fmt::LowerHex + fmt::UpperHex;
+
+    /// Length of the digest in bytes.
+    const OUTPUT_SIZE: usize;
+
+    /// Size in bytes of the blocks consumed by the compression function.
+    /// For the hashes built from other hashes' digests, ED2K and AICH,
+    /// it's the size of the pieces those digests are taken over.
+    const BLOCK_SIZE: usize;
+
+    /// Conventional name of the algorithm, e.g. "SHA-256".
+    const NAME: &'static str;
+
+    /// Start a new, empty hash.
+    fn new() -> Self;
+
+    /// Feed in the next piece of input.
+    fn update(&mut self, input: &[u8]);
+
+    /// Finish off and return the digest of everything fed in.
+    fn finalize(self) -> Self::Output;
+
+    /// Hash `input` in one go.
+    fn digest(input: &[u8]) -> Self::Output {
+        let mut hasher = Self::new();
+        hasher.update(input);
+        hasher.finalize()
+    }
+}
@@ -0,0 +1,147 @@
+use crate::buffer::BlockBuffer;
+use crate::{Digest, Output};
+
+fn compress(state: &mut [u32; 4], chunk: &[u8; 64]) {
+    // Little endian here too
+    let mut m = [0u32; 16];
+    for (word, int32_bytes) in m.iter_mut().zip(chunk.chunks_exact(4)) {
+        *word = u32::from_le_bytes(int32_bytes.try_into().unwrap());
+    }
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+
+    // Unrolled all the rounds for this one.  Why not!
+    a = (a.wrapping_add((b & c) | (!b) & d).wrapping_add(m[0])).rotate_left(3);
+    d = (d.wrapping_add((a & b) | (!a) & c).wrapping_add(m[1])).rotate_left(7);
+    c = (c.wrapping_add((d & a) | (!d) & b).wrapping_add(m[2])).rotate_left(11);
+    b = (b.wrapping_add((c & d) | (!c) & a).wrapping_add(m[3])).rotate_left(19);
+    a = (a.wrapping_add((b & c) | (!b) & d).wrapping_add(m[4])).rotate_left(3);
+    d = (d.wrapping_add((a & b) | (!a) & c).wrapping_add(m[5])).rotate_left(7);
+    c = (c.wrapping_add((d & a) | (!d) & b).wrapping_add(m[6])).rotate_left(11);
+    b = (b.wrapping_add((c & d) | (!c) & a).wrapping_add(m[7])).rotate_left(19);
+    a = (a.wrapping_add((b & c) | (!b) & d).wrapping_add(m[8])).rotate_left(3);
+    d = (d.wrapping_add((a & b) | (!a) & c).wrapping_add(m[9])).rotate_left(7);
+    c = (c.wrapping_add((d & a) | (!d) & b).wrapping_add(m[10])).rotate_left(11);
+    b = (b.wrapping_add((c & d) | (!c) & a).wrapping_add(m[11])).rotate_left(19);
+    a = (a.wrapping_add((b & c) | (!b) & d).wrapping_add(m[12])).rotate_left(3);
+    d = (d.wrapping_add((a & b) | (!a) & c).wrapping_add(m[13])).rotate_left(7);
+    c = (c.wrapping_add((d & a) | (!d) & b).wrapping_add(m[14])).rotate_left(11);
+    b = (b.wrapping_add((c & d) | (!c) & a).wrapping_add(m[15])).rotate_left(19);
+
+    a = (a.wrapping_add((b & c) | (b & d) | (c & d)).wrapping_add(m[0].wrapping_add(0x5a827999))).rotate_left(3);
+    d = (d.wrapping_add((a & b) | (a & c) | (b & c)).wrapping_add(m[4].wrapping_add(0x5a827999))).rotate_left(5);
+    c = (c.wrapping_add((d & a) | (d & b) | (a & b)).wrapping_add(m[8].wrapping_add(0x5a827999))).rotate_left(9);
+    b = (b.wrapping_add((c & d) | (c & a) | (d & a)).wrapping_add(m[12].wrapping_add(0x5a827999))).rotate_left(13);
+    a = (a.wrapping_add((b & c) | (b & d) | (c & d)).wrapping_add(m[1].wrapping_add(0x5a827999))).rotate_left(3);
+    d = (d.wrapping_add((a & b) | (a & c) | (b & c)).wrapping_add(m[5].wrapping_add(0x5a827999))).rotate_left(5);
+    c = (c.wrapping_add((d & a) | (d & b) | (a & b)).wrapping_add(m[9].wrapping_add(0x5a827999))).rotate_left(9);
+    b = (b.wrapping_add((c & d) | (c & a) | (d & a)).wrapping_add(m[13].wrapping_add(0x5a827999))).rotate_left(13);
+    a = (a.wrapping_add((b & c) | (b & d) | (c & d)).wrapping_add(m[2].wrapping_add(0x5a827999))).rotate_left(3);
+    d = (d.wrapping_add((a & b) | (a & c) | (b & c)).wrapping_add(m[6].wrapping_add(0x5a827999))).rotate_left(5);
+    c = (c.wrapping_add((d & a) | (d & b) | (a & b)).wrapping_add(m[10].wrapping_add(0x5a827999))).rotate_left(9);
+    b = (b.wrapping_add((c & d) | (c & a) | (d & a)).wrapping_add(m[14].wrapping_add(0x5a827999))).rotate_left(13);
+    a = (a.wrapping_add((b & c) | (b & d) | (c & d)).wrapping_add(m[3].wrapping_add(0x5a827999))).rotate_left(3);
+    d = (d.wrapping_add((a & b) | (a & c) | (b & c)).wrapping_add(m[7].wrapping_add(0x5a827999))).rotate_left(5);
+    c = (c.wrapping_add((d & a) | (d & b) | (a & b)).wrapping_add(m[11].wrapping_add(0x5a827999))).rotate_left(9);
+    b = (b.wrapping_add((c & d) | (c & a) | (d & a)).wrapping_add(m[15].wrapping_add(0x5a827999))).rotate_left(13);
+
+    a = (a.wrapping_add(b ^ c ^ d).wrapping_add(m[0].wrapping_add(0x6ed9eba1))).rotate_left(3);
+    d = (d.wrapping_add(a ^ b ^ c).wrapping_add(m[8].wrapping_add(0x6ed9eba1))).rotate_left(9);
+    c = (c.wrapping_add(d ^ a ^ b).wrapping_add(m[4].wrapping_add(0x6ed9eba1))).rotate_left(11);
+    b = (b.wrapping_add(c ^ d ^ a).wrapping_add(m[12].wrapping_add(0x6ed9eba1))).rotate_left(15);
+    a = (a.wrapping_add(b ^ c ^ d).wrapping_add(m[2].wrapping_add(0x6ed9eba1))).rotate_left(3);
+    d = (d.wrapping_add(a ^ b ^ c).wrapping_add(m[10].wrapping_add(0x6ed9eba1))).rotate_left(9);
+    c = (c.wrapping_add(d ^ a ^ b).wrapping_add(m[6].wrapping_add(0x6ed9eba1))).rotate_left(11);
+    b = (b.wrapping_add(c ^ d ^ a).wrapping_add(m[14].wrapping_add(0x6ed9eba1))).rotate_left(15);
+    a = (a.wrapping_add(b ^ c ^ d).wrapping_add(m[1].wrapping_add(0x6ed9eba1))).rotate_left(3);
+    d = (d.wrapping_add(a ^ b ^ c).wrapping_add(m[9].wrapping_add(0x6ed9eba1))).rotate_left(9);
+    c = (c.wrapping_add(d ^ a ^ b).wrapping_add(m[5].wrapping_add(0x6ed9eba1))).rotate_left(11);
+    b = (b.wrapping_add(c ^ d ^ a).wrapping_add(m[13].wrapping_add(0x6ed9eba1))).rotate_left(15);
+    a = (a.wrapping_add(b ^ c ^ d).wrapping_add(m[3].wrapping_add(0x6ed9eba1))).rotate_left(3);
+    d = (d.wrapping_add(a ^ b ^ c).wrapping_add(m[11].wrapping_add(0x6ed9eba1))).rotate_left(9);
+    c = (c.wrapping_add(d ^ a ^ b).wrapping_add(m[7].wrapping_add(0x6ed9eba1))).rotate_left(11);
+    b = (b.wrapping_add(c ^ d ^ a).wrapping_add(m[15].wrapping_add(0x6ed9eba1))).rotate_left(15);
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+}
+
+/// MD4, as specified in RFC 1320.
+///
+/// Input can be fed in with any number of calls to [`Md4::update`] before
+/// [`Md4::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Md4 {
+    state: [u32; 4],
+    buffer: BlockBuffer<64>,
+    length: u64,
+}
+
+impl Md4 {
+    pub fn new() -> Md4 {
+        Md4 {
+            state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476],
+            buffer: BlockBuffer::new(),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length = self.length.wrapping_add(input.len() as u64);
+        self.buffer.update(input, |chunk| compress(&mut self.state, chunk));
+    }
+
+    pub fn finalize(mut self) -> Output<16> {
+        // Message length in bits as a little endian u64
+        let length = self.length.wrapping_mul(8).to_le_bytes();
+        self.buffer.md_pad(&length, |chunk| compress(&mut self.state, chunk));
+
+        let mut result = [0; 16];
+        for (bytes, v) in result.chunks_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&v.to_le_bytes());
+        }
+
+        Output::new(result)
+    }
+}
+
+impl Default for Md4 {
+    fn default() -> Md4 {
+        Md4::new()
+    }
+}
+
+impl Digest for Md4 {
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "MD4";
+
+    type Output = Output<16>;
+
+    fn new() -> Md4 {
+        Md4::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Md4::update(self, input)
+    }
+
+    fn finalize(self) -> Output<16> {
+        Md4::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<16> {
+        md4(input)
+    }
+}
+
+pub fn md4(input: &[u8]) -> Output<16> {
+    let mut hasher = Md4::new();
+    hasher.update(input);
+    hasher.finalize()
+}
@@ -0,0 +1,136 @@
+use crate::buffer::BlockBuffer;
+use crate::{Digest, Output};
+
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+fn compress(state: &mut [u32; 4], chunk: &[u8; 64]) {
+    // Little endian here too
+    let mut m = [0u32; 16];
+    for (word, int32_bytes) in m.iter_mut().zip(chunk.chunks_exact(4)) {
+        *word = u32::from_le_bytes(int32_bytes.try_into().unwrap());
+    }
+
+    let mut v = *state;
+
+    rounds(&mut v, &m, 0, |b, c, d| (b & c) | ((!b) & d), |i| i);
+    rounds(&mut v, &m, 16, |b, c, d| (d & b) | ((!d) & c), |i| (5 * i + 1) % 16);
+    rounds(&mut v, &m, 32, |b, c, d| b ^ c ^ d, |i| (3 * i + 5) % 16);
+    rounds(&mut v, &m, 48, |b, c, d| c ^ (b | (!d)), |i| (7 * i) % 16);
+
+    for i in 0..4 {
+        state[i] = state[i].wrapping_add(v[i]);
+    }
+}
+
+// One round: the sixteen steps from `start` on, all with the same f and the
+// same rule g for picking a message word
+#[inline(always)]
+fn rounds(
+    v: &mut [u32; 4],
+    m: &[u32; 16],
+    start: usize,
+    f: impl Fn(u32, u32, u32) -> u32,
+    g: impl Fn(usize) -> usize,
+) {
+    let [mut a, mut b, mut c, mut d] = *v;
+
+    for i in start..start + 16 {
+        let f = f(b, c, d).wrapping_add(a).wrapping_add(K[i]).wrapping_add(m[g(i)]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(f.rotate_left(SHIFTS[i]));
+    }
+
+    *v = [a, b, c, d];
+}
+
+/// MD5, as specified in RFC 1321.
+///
+/// Input can be fed in with any number of calls to [`Md5::update`] before
+/// [`Md5::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Md5 {
+    state: [u32; 4],
+    buffer: BlockBuffer<64>,
+    length: u64,
+}
+
+impl Md5 {
+    pub fn new() -> Md5 {
+        Md5 {
+            state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476],
+            buffer: BlockBuffer::new(),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length = self.length.wrapping_add(input.len() as u64);
+        self.buffer.update(input, |chunk| compress(&mut self.state, chunk));
+    }
+
+    pub fn finalize(mut self) -> Output<16> {
+        // Message length in bits as a little endian u64
+        let length = self.length.wrapping_mul(8).to_le_bytes();
+        self.buffer.md_pad(&length, |chunk| compress(&mut self.state, chunk));
+
+        let mut result = [0; 16];
+        for (bytes, v) in result.chunks_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&v.to_le_bytes());
+        }
+
+        Output::new(result)
+    }
+}
+
+impl Default for Md5 {
+    fn default() -> Md5 {
+        Md5::new()
+    }
+}
+
+impl Digest for Md5 {
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "MD5";
+
+    type Output = Output<16>;
+
+    fn new() -> Md5 {
+        Md5::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Md5::update(self, input)
+    }
+
+    fn finalize(self) -> Output<16> {
+        Md5::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<16> {
+        md5(input)
+    }
+}
+
+pub fn md5(input: &[u8]) -> Output<16> {
+    let mut hasher = Md5::new();
+    hasher.update(input);
+    hasher.finalize()
+}
@@ -0,0 +1,236 @@
+//! The NT hash of Windows passwords, and the NTLMv2 challenge-response
+//! built on it, as specified in MS-NLMP.
+//!
+//! The NT hash is MD4 over the password in UTF-16LE.  NTLMv2 keys HMAC-MD5
+//! with it to get a per-user response key, then answers a server's
+//! challenge with an HMAC over the challenge and a blob of the client's.
+//!
+//! Captured exchanges are usually passed around as NetNTLMv2 strings,
+//! which [`NetNtlmv2`] parses and checks passwords against:
+//!
+//! ```
+//! use hash_functions::ntlm::NetNtlmv2;
+//!
+//! let captured: NetNtlmv2 = "User::Domain:0123456789abcdef:\
+//!     68cd0ab851e51c96aabc927bebef6a1c:\
+//!     01010000000000000000000000000000aaaaaaaaaaaaaaaa00000000\
+//!     02000c0044006f006d00610069006e0001000c005300650072007600650072000000000000000000"
+//!     .parse()
+//!     .unwrap();
+//!
+//! assert!(captured.verify("Password"));
+//! assert!(!captured.verify("password"));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::hmac::Hmac;
+use crate::md4::Md4;
+use crate::md5::Md5;
+use crate::output::parse_hex;
+use crate::{Digest, Output};
+
+// Two version bytes, six reserved, the timestamp, the client challenge and
+// four more reserved, all ahead of the target information
+const BLOB_HEADER_SIZE: usize = 28;
+
+/// The NT hash (or NTOWFv1) of a password: MD4 of its UTF-16LE encoding.
+/// What Windows stores, and all an attacker needs to authenticate as the
+/// user.
+pub fn nt_hash(password: &str) -> Output<16> {
+    Md4::digest(&utf16le(password))
+}
+
+/// NTOWFv2, the NTLMv2 response key for a user: HMAC-MD5 keyed with their
+/// NT hash over their name in upper case followed by the domain as given.
+///
+/// The name is upper-cased a character at a time, as Windows does, so
+/// characters such as 'ß' whose upper case is longer are left alone.
+pub fn ntowf_v2(nt_hash: &Output<16>, user: &str, domain: &str) -> Output<16> {
+    let user: String = user.chars().map(upper_case).collect();
+
+    let mut mac = Hmac::<Md5>::new(nt_hash.as_ref());
+    mac.update(&utf16le(&user));
+    mac.update(&utf16le(domain));
+    mac.finalize()
+}
+
+/// The blob a client sends along with its NTLMv2 response: `timestamp` in
+/// Windows FILETIME units (100 ns ticks since 1601), its own 8-byte
+/// challenge, and `target_info`, the AV pairs from the server's CHALLENGE
+/// message, including the terminating `MsvAvEOL`.
+pub fn blob(timestamp: u64, client_challenge: &[u8; 8], target_info: &[u8]) -> Vec<u8> {
+    let mut blob = Vec::with_capacity(BLOB_HEADER_SIZE + target_info.len() + 4);
+    blob.extend_from_slice(&[1, 1, 0, 0, 0, 0, 0, 0]);
+    blob.extend_from_slice(&timestamp.to_le_bytes());
+    blob.extend_from_slice(client_challenge);
+    blob.extend_from_slice(&[0; 4]);
+    blob.extend_from_slice(target_info);
+    blob.extend_from_slice(&[0; 4]);
+    blob
+}
+
+/// NTProofStr: HMAC-MD5 keyed with the response key over the server's
+/// challenge and the client's blob.
+pub fn nt_proof(response_key: &Output<16>, server_challenge: &[u8; 8], blob: &[u8]) -> Output<16> {
+    let mut mac = Hmac::<Md5>::new(response_key.as_ref());
+    mac.update(server_challenge);
+    mac.update(blob);
+    mac.finalize()
+}
+
+/// The NTLMv2 response that goes in the AUTHENTICATE message: NTProofStr
+/// followed by the blob.
+pub fn ntlmv2_response(response_key: &Output<16>, server_challenge: &[u8; 8], blob: &[u8]) -> Vec<u8> {
+    let mut response = nt_proof(response_key, server_challenge, blob).as_ref().to_vec();
+    response.extend_from_slice(blob);
+    response
+}
+
+/// The session base key both sides derive from a successful exchange:
+/// HMAC-MD5 keyed with the response key over NTProofStr.
+pub fn session_base_key(response_key: &Output<16>, nt_proof: &Output<16>) -> Output<16> {
+    let mut mac = Hmac::<Md5>::new(response_key.as_ref());
+    mac.update(nt_proof.as_ref());
+    mac.finalize()
+}
+
+/// The LMv2 response sent alongside the NTLMv2 one: HMAC-MD5 over both
+/// challenges, followed by the client's.
+pub fn lmv2_response(response_key: &Output<16>, server_challenge: &[u8; 8], client_challenge: &[u8; 8]) -> [u8; 24] {
+    let mut mac = Hmac::<Md5>::new(response_key.as_ref());
+    mac.update(server_challenge);
+    mac.update(client_challenge);
+
+    let mut response = [0; 24];
+    response[..16].copy_from_slice(mac.finalize().as_ref());
+    response[16..].copy_from_slice(client_challenge);
+    response
+}
+
+/// A captured NTLMv2 exchange in the NetNTLMv2 format that capture and
+/// cracking tools share:
+///
+/// ```text
+/// user::domain:server challenge:NTProofStr:blob
+/// ```
+///
+/// with the last three in hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetNtlmv2 {
+    pub user: String,
+    pub domain: String,
+    pub server_challenge: [u8; 8],
+    pub nt_proof: Output<16>,
+    pub blob: Vec<u8>,
+}
+
+impl NetNtlmv2 {
+    /// Whether the client knew `password`.
+    pub fn verify(&self, password: &str) -> bool {
+        self.verify_nt_hash(&nt_hash(password))
+    }
+
+    /// Whether the client knew the password with NT hash `nt_hash`, in
+    /// constant time.
+    pub fn verify_nt_hash(&self, nt_hash: &Output<16>) -> bool {
+        let response_key = ntowf_v2(nt_hash, &self.user, &self.domain);
+        nt_proof(&response_key, &self.server_challenge, &self.blob).ct_eq(&self.nt_proof)
+    }
+
+    /// The session base key the exchange set up, given the password's NT
+    /// hash.
+    pub fn session_base_key(&self, nt_hash: &Output<16>) -> Output<16> {
+        session_base_key(&ntowf_v2(nt_hash, &self.user, &self.domain), &self.nt_proof)
+    }
+}
+
+impl fmt::Display for NetNtlmv2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}::{}:", self.user, self.domain)?;
+        for byte in self.server_challenge {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(f, ":{}:", self.nt_proof)?;
+        for byte in &self.blob {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NetNtlmv2 {
+    type Err = ParseNetNtlmv2Error;
+
+    fn from_str(s: &str) -> Result<NetNtlmv2, ParseNetNtlmv2Error> {
+        use ParseNetNtlmv2Error::*;
+
+        let fields: Vec<&str> = s.split(':').collect();
+        let [user, empty, domain, server_challenge, proof, blob] = fields[..] else {
+            return Err(Format);
+        };
+        if user.is_empty() || !empty.is_empty() {
+            return Err(Format);
+        }
+
+        let mut challenge = [0; 8];
+        let mut nt_proof = [0; 16];
+        if server_challenge.len() != 16 || proof.len() != 32 || blob.len() < 2 * BLOB_HEADER_SIZE {
+            return Err(Length);
+        }
+        if !blob.len().is_multiple_of(2) {
+            return Err(Encoding);
+        }
+
+        let mut blob_bytes = vec![0; blob.len() / 2];
+        parse_hex(server_challenge, &mut challenge).map_err(|_| Encoding)?;
+        parse_hex(proof, &mut nt_proof).map_err(|_| Encoding)?;
+        parse_hex(blob, &mut blob_bytes).map_err(|_| Encoding)?;
+
+        Ok(NetNtlmv2 {
+            user: user.to_string(),
+            domain: domain.to_string(),
+            server_challenge: challenge,
+            nt_proof: Output::new(nt_proof),
+            blob: blob_bytes,
+        })
+    }
+}
+
+/// Why a string couldn't be parsed as a NetNTLMv2 one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseNetNtlmv2Error {
+    /// The string isn't `user::domain:challenge:proof:blob`.
+    Format,
+    /// The challenge, proof or blob isn't valid hex.
+    Encoding,
+    /// The challenge or proof is the wrong length, or the blob too short.
+    Length,
+}
+
+impl fmt::Display for ParseNetNtlmv2Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseNetNtlmv2Error::Format => write!(f, "not a NetNTLMv2 string"),
+            ParseNetNtlmv2Error::Encoding => write!(f, "invalid hex"),
+            ParseNetNtlmv2Error::Length => write!(f, "challenge, proof or blob has the wrong length"),
+        }
+    }
+}
+
+impl Error for ParseNetNtlmv2Error {}
+
+fn utf16le(s: &str) -> Vec<u8> {
+    s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+// The upper case of `c` where that's a single character, and `c` otherwise
+fn upper_case(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => c,
+    }
+}
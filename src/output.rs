@@ -0,0 +1,249 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The raw bytes of a fixed-length digest.
+///
+/// Formats as lowercase hex through `Display`, and parses back from hex (in
+/// either case) through `FromStr`.  Comparisons, including `==`, take the
+/// same time whichever byte differs.
+#[derive(Clone, Copy)]
+pub struct Output<const N: usize>([u8; N]);
+
+impl<const N: usize> Output<N> {
+    pub fn new(bytes: [u8; N]) -> Output<N> {
+        Output(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> [u8; N] {
+        self.0
+    }
+
+    /// Compare against `other` without short-circuiting on the first
+    /// difference, so that the time taken leaks nothing about where the two
+    /// digests diverge.
+    pub fn ct_eq(&self, other: &Output<N>) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Output<N> {
+    fn from(bytes: [u8; N]) -> Output<N> {
+        Output(bytes)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Output<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> PartialEq for Output<N> {
+    fn eq(&self, other: &Output<N>) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl<const N: usize> Eq for Output<N> {}
+
+impl<const N: usize> fmt::Display for Output<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, &self.0, false)
+    }
+}
+
+impl<const N: usize> fmt::Debug for Output<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Output({})", self)
+    }
+}
+
+impl<const N: usize> fmt::LowerHex for Output<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, &self.0, false)
+    }
+}
+
+impl<const N: usize> fmt::UpperHex for Output<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, &self.0, true)
+    }
+}
+
+impl<const N: usize> FromStr for Output<N> {
+    type Err = ParseOutputError;
+
+    fn from_str(s: &str) -> Result<Output<N>, ParseOutputError> {
+        if s.len() != N * 2 {
+            return Err(ParseOutputError::Length);
+        }
+
+        let mut bytes = [0; N];
+        parse_hex(s, &mut bytes)?;
+        Ok(Output(bytes))
+    }
+}
+
+/// The raw bytes of a digest whose length is chosen at runtime, up to `MAX`
+/// bytes.  Used by algorithms such as BLAKE2 that take the digest length as
+/// a parameter; otherwise behaves just like [`Output`].
+#[derive(Clone, Copy)]
+pub struct VarOutput<const MAX: usize> {
+    bytes: [u8; MAX],
+    len: usize,
+}
+
+impl<const MAX: usize> VarOutput<MAX> {
+    /// Panics if `bytes` is empty or longer than `MAX`.
+    pub fn new(bytes: &[u8]) -> VarOutput<MAX> {
+        assert!(!bytes.is_empty() && bytes.len() <= MAX, "Digest length out of range");
+
+        let mut result = VarOutput {
+            bytes: [0; MAX],
+            len: bytes.len(),
+        };
+        result.bytes[..bytes.len()].copy_from_slice(bytes);
+        result
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Like [`Output::ct_eq`].  Digests of different lengths are never equal;
+    /// the lengths themselves aren't considered secret.
+    pub fn ct_eq(&self, other: &VarOutput<MAX>) -> bool {
+        self.len == other.len && ct_eq(self.as_bytes(), other.as_bytes())
+    }
+}
+
+impl<const MAX: usize> AsRef<[u8]> for VarOutput<MAX> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const MAX: usize> PartialEq for VarOutput<MAX> {
+    fn eq(&self, other: &VarOutput<MAX>) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl<const MAX: usize> Eq for VarOutput<MAX> {}
+
+impl<const MAX: usize> fmt::Display for VarOutput<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.as_bytes(), false)
+    }
+}
+
+impl<const MAX: usize> fmt::Debug for VarOutput<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VarOutput({})", self)
+    }
+}
+
+impl<const MAX: usize> fmt::LowerHex for VarOutput<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.as_bytes(), false)
+    }
+}
+
+impl<const MAX: usize> fmt::UpperHex for VarOutput<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_hex(f, self.as_bytes(), true)
+    }
+}
+
+impl<const MAX: usize> FromStr for VarOutput<MAX> {
+    type Err = ParseOutputError;
+
+    fn from_str(s: &str) -> Result<VarOutput<MAX>, ParseOutputError> {
+        if s.is_empty() || !s.len().is_multiple_of(2) || s.len() > MAX * 2 {
+            return Err(ParseOutputError::Length);
+        }
+
+        let mut result = VarOutput {
+            bytes: [0; MAX],
+            len: s.len() / 2,
+        };
+        parse_hex(s, &mut result.bytes[..s.len() / 2])?;
+        Ok(result)
+    }
+}
+
+/// Why a hex string couldn't be parsed as a digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseOutputError {
+    /// The string doesn't encode the right number of bytes.
+    Length,
+    /// The string contains something other than hex digits.
+    Character,
+}
+
+impl fmt::Display for ParseOutputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseOutputError::Length => write!(f, "hex digest has the wrong length"),
+            ParseOutputError::Character => write!(f, "hex digest contains a non-hex character"),
+        }
+    }
+}
+
+impl Error for ParseOutputError {}
+
+/// Compare two byte strings in time that depends only on their lengths.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut difference = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        difference |= x ^ y;
+    }
+
+    difference == 0
+}
+
+fn write_hex(f: &mut fmt::Formatter, bytes: &[u8], upper: bool) -> fmt::Result {
+    for b in bytes {
+        if upper {
+            write!(f, "{:02X}", b)?;
+        } else {
+            write!(f, "{:02x}", b)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn parse_hex(s: &str, out: &mut [u8]) -> Result<(), ParseOutputError> {
+    fn nibble(c: u8) -> Result<u8, ParseOutputError> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(ParseOutputError::Character),
+        }
+    }
+
+    for (byte, pair) in out.iter_mut().zip(s.as_bytes().chunks(2)) {
+        *byte = (nibble(pair[0])? << 4) | nibble(pair[1])?;
+    }
+
+    Ok(())
+}
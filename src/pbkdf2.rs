@@ -0,0 +1,59 @@
+//! PBKDF2, the password-based key derivation function of RFC 8018, with
+//! HMAC over any of the crate's hashes as its pseudorandom function.
+//!
+//! ```
+//! use hash_functions::pbkdf2::pbkdf2;
+//! use hash_functions::sha1::Sha1;
+//!
+//! let mut key = [0; 20];
+//! pbkdf2::<Sha1>(b"password", b"salt", 2, &mut key);
+//!
+//! assert_eq!(key[..4], [0xea, 0x6c, 0x01, 0x4d]);
+//! ```
+
+use crate::hmac::Hmac;
+use crate::Digest;
+
+/// Fill `output` with a key derived from `password` and `salt` by
+/// `iterations` rounds of HMAC-`D`.
+///
+/// The password is the HMAC key throughout, so its pad states are worked
+/// out once and every round starts from a copy of them: each round then
+/// costs only the compressions for its own short message.
+///
+/// # Panics
+///
+/// If `iterations` is zero, or `output` is longer than the 2^32 - 1 hash
+/// lengths that RFC 8018 allows.
+pub fn pbkdf2<D: Digest + Clone>(password: &[u8], salt: &[u8], iterations: u32, output: &mut [u8]) {
+    assert!(iterations > 0, "PBKDF2 needs at least one iteration");
+    assert!(
+        output.len().div_ceil(D::OUTPUT_SIZE) as u64 <= u32::MAX as u64,
+        "PBKDF2 can derive at most 2^32 - 1 blocks"
+    );
+
+    let prf = Hmac::<D>::new(password);
+
+    for (i, block) in output.chunks_mut(D::OUTPUT_SIZE).enumerate() {
+        // T_i = U_1 ^ U_2 ^ ... ^ U_c, where U_1 = PRF(P, S || INT(i)) and
+        // U_j = PRF(P, U_{j-1}).  A short last block is the front of T_i,
+        // which is the XOR of the fronts of the U_j, so it can be built up
+        // in place.
+        let mut mac = prf.clone();
+        mac.update(salt);
+        mac.update(&(i as u32 + 1).to_be_bytes());
+        let mut u = mac.finalize();
+
+        block.copy_from_slice(&u.as_ref()[..block.len()]);
+
+        for _ in 1..iterations {
+            let mut mac = prf.clone();
+            mac.update(u.as_ref());
+            u = mac.finalize();
+
+            for (t, u) in block.iter_mut().zip(u.as_ref()) {
+                *t ^= u;
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// RIPEMD-128 from the same paper as RIPEMD-160: four rounds of the same
+// message ordering and rotations on four-word lines, with no E register.
+//
+// https://homes.esat.kuleuven.be/~bosselae/ripemd160/pdf/AB-9601/AB-9601.pdf
+
+use crate::buffer::BlockBuffer;
+use crate::ripemd160::{
+    constant_k, func, load_words, word_select, R_OFFSETS, R_P_OFFSETS, ROTATIONS, ROTATIONS_P,
+};
+use crate::{Digest, Output};
+
+// The right line's constants differ from RIPEMD-160's in the fourth round,
+// which is the last one here.
+pub(crate) fn constant_k_p(j: usize) -> u32 {
+    match j {
+        0..=15 => 0x50A28BE6u32,
+        16..=31 => 0x5C4DD124u32,
+        32..=47 => 0x6D703EF3u32,
+        48..=63 => 0x00000000u32,
+        _ => {
+            unreachable!();
+        }
+    }
+}
+
+// a corresponds to A in the original paper; a_p corresponds to A'
+fn compress(state: &mut [u32; 4], chunk: &[u8; 64]) {
+    let x = load_words(chunk);
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut a_p = state[0];
+    let mut b_p = state[1];
+    let mut c_p = state[2];
+    let mut d_p = state[3];
+
+    let mut t;
+
+    for j in 0..64 {
+        t = a
+            .wrapping_add(func(j, b, c, d))
+            .wrapping_add(word_select(j, &x, &R_OFFSETS))
+            .wrapping_add(constant_k(j))
+            .rotate_left(ROTATIONS[j]);
+        a = d;
+        d = c;
+        c = b;
+        b = t;
+
+        t = a_p
+            .wrapping_add(func(63 - j, b_p, c_p, d_p))
+            .wrapping_add(word_select(j, &x, &R_P_OFFSETS))
+            .wrapping_add(constant_k_p(j))
+            .rotate_left(ROTATIONS_P[j]);
+        a_p = d_p;
+        d_p = c_p;
+        c_p = b_p;
+        b_p = t;
+    }
+
+    t = state[1].wrapping_add(c).wrapping_add(d_p);
+    state[1] = state[2].wrapping_add(d).wrapping_add(a_p);
+    state[2] = state[3].wrapping_add(a).wrapping_add(b_p);
+    state[3] = state[0].wrapping_add(b).wrapping_add(c_p);
+    state[0] = t;
+}
+
+/// RIPEMD-128, as specified in the AB-9601 paper.
+///
+/// Input can be fed in with any number of calls to [`Ripemd128::update`]
+/// before [`Ripemd128::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Ripemd128 {
+    state: [u32; 4],
+    buffer: BlockBuffer<64>,
+    length: u64,
+}
+
+impl Ripemd128 {
+    pub fn new() -> Ripemd128 {
+        Ripemd128 {
+            state: [
+                0x67452301,
+                0xEFCDAB89,
+                0x98BADCFE,
+                0x10325476,
+            ],
+            buffer: BlockBuffer::new(),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length = self.length.wrapping_add(input.len() as u64);
+        self.buffer.update(input, |chunk| compress(&mut self.state, chunk));
+    }
+
+    pub fn finalize(mut self) -> Output<16> {
+        // Message length in bits as a little endian u64
+        let length = self.length.wrapping_mul(8).to_le_bytes();
+        self.buffer.md_pad(&length, |chunk| compress(&mut self.state, chunk));
+
+        let mut result = [0; 16];
+        for (bytes, v) in result.chunks_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&v.to_le_bytes());
+        }
+
+        Output::new(result)
+    }
+}
+
+impl Default for Ripemd128 {
+    fn default() -> Ripemd128 {
+        Ripemd128::new()
+    }
+}
+
+impl Digest for Ripemd128 {
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "RIPEMD-128";
+
+    type Output = Output<16>;
+
+    fn new() -> Ripemd128 {
+        Ripemd128::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Ripemd128::update(self, input)
+    }
+
+    fn finalize(self) -> Output<16> {
+        Ripemd128::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<16> {
+        ripemd128(input)
+    }
+}
+
+pub fn ripemd128(input: &[u8]) -> Output<16> {
+    let mut hasher = Ripemd128::new();
+    hasher.update(input);
+    hasher.finalize()
+}
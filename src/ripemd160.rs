@@ -0,0 +1,211 @@
+use crate::buffer::BlockBuffer;
+use crate::{Digest, Output};
+
+// Non-linear functions at bit-level
+pub(crate) fn func(j: usize, x: u32, y: u32, z: u32) -> u32 {
+    match j {
+        0..=15 => x ^ y ^ z,
+        16..=31 => (x & y) | (!x & z),
+        32..=47 => (x | !y) ^ z,
+        48..=63 => (x & z) | (y & !z),
+        64..=79 => x ^ (y | !z),
+        _ => {
+            unreachable!();
+        }
+    }
+}
+
+pub(crate) const R_OFFSETS: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5,
+    2, 14, 11, 8, 3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, 1, 9, 11, 10, 0, 8, 12, 4,
+    13, 3, 7, 15, 14, 5, 6, 2, 4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+
+pub(crate) const R_P_OFFSETS: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, 6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12,
+    4, 9, 1, 2, 15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, 8, 6, 4, 1, 3, 11, 15, 0, 5,
+    12, 2, 13, 9, 7, 10, 14, 12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+// The block as the sixteen little-endian words X[0..15] that the rounds
+// select from.
+pub(crate) fn load_words(chunk: &[u8; 64]) -> [u32; 16] {
+    let mut x = [0u32; 16];
+    for (word, int32_bytes) in x.iter_mut().zip(chunk.chunks_exact(4)) {
+        *word = u32::from_le_bytes(int32_bytes.try_into().unwrap());
+    }
+
+    x
+}
+
+pub(crate) fn word_select(j: usize, x: &[u32; 16], offsets: &[usize; 80]) -> u32 {
+    x[offsets[j]]
+}
+
+pub(crate) fn constant_k(j: usize) -> u32 {
+    match j {
+        0..=15 => 0x00000000u32,
+        16..=31 => 0x5A827999u32,
+        32..=47 => 0x6ED9EBA1u32,
+        48..=63 => 0x8F1BBCDCu32,
+        64..=79 => 0xA953FD4Eu32,
+        _ => {
+            unreachable!();
+        }
+    }
+}
+
+pub(crate) fn constant_k_p(j: usize) -> u32 {
+    match j {
+        0..=15 => 0x50A28BE6u32,
+        16..=31 => 0x5C4DD124u32,
+        32..=47 => 0x6D703EF3u32,
+        48..=63 => 0x7A6D76E9u32,
+        64..=79 => 0x00000000u32,
+        _ => {
+            unreachable!();
+        }
+    }
+}
+
+pub(crate) const ROTATIONS: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, 7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15,
+    9, 11, 7, 13, 12, 11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, 11, 12, 14, 15, 14,
+    15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, 9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+
+pub(crate) const ROTATIONS_P: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, 9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12,
+    7, 6, 15, 13, 11, 9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, 15, 5, 8, 11, 14, 14,
+    6, 14, 6, 9, 12, 9, 12, 5, 15, 8, 8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+// Based on pseudocode from Appendix A:
+// https://homes.esat.kuleuven.be/~bosselae/ripemd160/pdf/AB-9601/AB-9601.pdf
+//
+// a corresponds to A in the original paper; a_p corresponds to A'
+fn compress(state: &mut [u32; 5], chunk: &[u8; 64]) {
+    let x = load_words(chunk);
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut e = state[4];
+    let mut a_p = state[0];
+    let mut b_p = state[1];
+    let mut c_p = state[2];
+    let mut d_p = state[3];
+    let mut e_p = state[4];
+
+    let mut t;
+
+    for j in 0..80 {
+        t = a
+            .wrapping_add(func(j, b, c, d))
+            .wrapping_add(word_select(j, &x, &R_OFFSETS))
+            .wrapping_add(constant_k(j))
+            .rotate_left(ROTATIONS[j])
+            .wrapping_add(e);
+        a = e;
+        e = d;
+        d = c.rotate_left(10);
+        c = b;
+        b = t;
+
+        t = a_p
+            .wrapping_add(func(79 - j, b_p, c_p, d_p))
+            .wrapping_add(word_select(j, &x, &R_P_OFFSETS))
+            .wrapping_add(constant_k_p(j))
+            .rotate_left(ROTATIONS_P[j])
+            .wrapping_add(e_p);
+
+        a_p = e_p;
+        e_p = d_p;
+        d_p = c_p.rotate_left(10);
+        c_p = b_p;
+        b_p = t;
+    }
+
+    t = state[1].wrapping_add(c).wrapping_add(d_p);
+    state[1] = state[2].wrapping_add(d).wrapping_add(e_p);
+    state[2] = state[3].wrapping_add(e).wrapping_add(a_p);
+    state[3] = state[4].wrapping_add(a).wrapping_add(b_p);
+    state[4] = state[0].wrapping_add(b).wrapping_add(c_p);
+    state[0] = t;
+}
+
+/// RIPEMD-160, as specified in the AB-9601 paper.
+///
+/// Input can be fed in with any number of calls to [`Ripemd160::update`]
+/// before [`Ripemd160::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Ripemd160 {
+    state: [u32; 5],
+    buffer: BlockBuffer<64>,
+    length: u64,
+}
+
+impl Ripemd160 {
+    pub fn new() -> Ripemd160 {
+        Ripemd160 {
+            state: [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0],
+            buffer: BlockBuffer::new(),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length = self.length.wrapping_add(input.len() as u64);
+        self.buffer.update(input, |chunk| compress(&mut self.state, chunk));
+    }
+
+    pub fn finalize(mut self) -> Output<20> {
+        // Message length in bits as a little endian u64
+        let length = self.length.wrapping_mul(8).to_le_bytes();
+        self.buffer.md_pad(&length, |chunk| compress(&mut self.state, chunk));
+
+        let mut result = [0; 20];
+        for (bytes, v) in result.chunks_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&v.to_le_bytes());
+        }
+
+        Output::new(result)
+    }
+}
+
+impl Default for Ripemd160 {
+    fn default() -> Ripemd160 {
+        Ripemd160::new()
+    }
+}
+
+impl Digest for Ripemd160 {
+    const OUTPUT_SIZE: usize = 20;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "RIPEMD-160";
+
+    type Output = Output<20>;
+
+    fn new() -> Ripemd160 {
+        Ripemd160::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Ripemd160::update(self, input)
+    }
+
+    fn finalize(self) -> Output<20> {
+        Ripemd160::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<20> {
+        ripemd160(input)
+    }
+}
+
+pub fn ripemd160(input: &[u8]) -> Output<20> {
+    let mut hasher = Ripemd160::new();
+    hasher.update(input);
+    hasher.finalize()
+}
@@ -0,0 +1,154 @@
+// RIPEMD-256: RIPEMD-128's two lines kept apart as a double-width state.
+// Rather than being combined at the end of each block, the lines trade one
+// register after each round.
+//
+// https://homes.esat.kuleuven.be/~bosselae/ripemd160/pdf/AB-9601/AB-9601.pdf
+
+use std::mem;
+
+use crate::buffer::BlockBuffer;
+use crate::ripemd128::constant_k_p;
+use crate::ripemd160::{
+    constant_k, func, load_words, word_select, R_OFFSETS, R_P_OFFSETS, ROTATIONS, ROTATIONS_P,
+};
+use crate::{Digest, Output};
+
+// a corresponds to A in the original paper; a_p corresponds to A'
+fn compress(state: &mut [u32; 8], chunk: &[u8; 64]) {
+    let x = load_words(chunk);
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut a_p = state[4];
+    let mut b_p = state[5];
+    let mut c_p = state[6];
+    let mut d_p = state[7];
+
+    let mut t;
+
+    for j in 0..64 {
+        t = a
+            .wrapping_add(func(j, b, c, d))
+            .wrapping_add(word_select(j, &x, &R_OFFSETS))
+            .wrapping_add(constant_k(j))
+            .rotate_left(ROTATIONS[j]);
+        a = d;
+        d = c;
+        c = b;
+        b = t;
+
+        t = a_p
+            .wrapping_add(func(63 - j, b_p, c_p, d_p))
+            .wrapping_add(word_select(j, &x, &R_P_OFFSETS))
+            .wrapping_add(constant_k_p(j))
+            .rotate_left(ROTATIONS_P[j]);
+        a_p = d_p;
+        d_p = c_p;
+        c_p = b_p;
+        b_p = t;
+
+        match j {
+            15 => mem::swap(&mut a, &mut a_p),
+            31 => mem::swap(&mut b, &mut b_p),
+            47 => mem::swap(&mut c, &mut c_p),
+            63 => mem::swap(&mut d, &mut d_p),
+            _ => {}
+        }
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+    state[4] = state[4].wrapping_add(a_p);
+    state[5] = state[5].wrapping_add(b_p);
+    state[6] = state[6].wrapping_add(c_p);
+    state[7] = state[7].wrapping_add(d_p);
+}
+
+/// RIPEMD-256, as specified in the AB-9601 paper.  Only as strong as
+/// RIPEMD-128: the extra width is for applications that want a longer digest.
+///
+/// Input can be fed in with any number of calls to [`Ripemd256::update`]
+/// before [`Ripemd256::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Ripemd256 {
+    state: [u32; 8],
+    buffer: BlockBuffer<64>,
+    length: u64,
+}
+
+impl Ripemd256 {
+    pub fn new() -> Ripemd256 {
+        Ripemd256 {
+            state: [
+                0x67452301,
+                0xEFCDAB89,
+                0x98BADCFE,
+                0x10325476,
+                0x76543210,
+                0xFEDCBA98,
+                0x89ABCDEF,
+                0x01234567,
+            ],
+            buffer: BlockBuffer::new(),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length = self.length.wrapping_add(input.len() as u64);
+        self.buffer.update(input, |chunk| compress(&mut self.state, chunk));
+    }
+
+    pub fn finalize(mut self) -> Output<32> {
+        // Message length in bits as a little endian u64
+        let length = self.length.wrapping_mul(8).to_le_bytes();
+        self.buffer.md_pad(&length, |chunk| compress(&mut self.state, chunk));
+
+        let mut result = [0; 32];
+        for (bytes, v) in result.chunks_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&v.to_le_bytes());
+        }
+
+        Output::new(result)
+    }
+}
+
+impl Default for Ripemd256 {
+    fn default() -> Ripemd256 {
+        Ripemd256::new()
+    }
+}
+
+impl Digest for Ripemd256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "RIPEMD-256";
+
+    type Output = Output<32>;
+
+    fn new() -> Ripemd256 {
+        Ripemd256::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Ripemd256::update(self, input)
+    }
+
+    fn finalize(self) -> Output<32> {
+        Ripemd256::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<32> {
+        ripemd256(input)
+    }
+}
+
+pub fn ripemd256(input: &[u8]) -> Output<32> {
+    let mut hasher = Ripemd256::new();
+    hasher.update(input);
+    hasher.finalize()
+}
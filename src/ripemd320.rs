@@ -0,0 +1,164 @@
+// RIPEMD-320: RIPEMD-160's two lines kept apart as a double-width state,
+// trading one register after each round as RIPEMD-256 does.
+//
+// https://homes.esat.kuleuven.be/~bosselae/ripemd160/pdf/AB-9601/AB-9601.pdf
+
+use std::mem;
+
+use crate::buffer::BlockBuffer;
+use crate::ripemd160::{
+    constant_k, constant_k_p, func, load_words, word_select, R_OFFSETS, R_P_OFFSETS, ROTATIONS,
+    ROTATIONS_P,
+};
+use crate::{Digest, Output};
+
+// a corresponds to A in the original paper; a_p corresponds to A'
+fn compress(state: &mut [u32; 10], chunk: &[u8; 64]) {
+    let x = load_words(chunk);
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut e = state[4];
+    let mut a_p = state[5];
+    let mut b_p = state[6];
+    let mut c_p = state[7];
+    let mut d_p = state[8];
+    let mut e_p = state[9];
+
+    let mut t;
+
+    for j in 0..80 {
+        t = a
+            .wrapping_add(func(j, b, c, d))
+            .wrapping_add(word_select(j, &x, &R_OFFSETS))
+            .wrapping_add(constant_k(j))
+            .rotate_left(ROTATIONS[j])
+            .wrapping_add(e);
+        a = e;
+        e = d;
+        d = c.rotate_left(10);
+        c = b;
+        b = t;
+
+        t = a_p
+            .wrapping_add(func(79 - j, b_p, c_p, d_p))
+            .wrapping_add(word_select(j, &x, &R_P_OFFSETS))
+            .wrapping_add(constant_k_p(j))
+            .rotate_left(ROTATIONS_P[j])
+            .wrapping_add(e_p);
+        a_p = e_p;
+        e_p = d_p;
+        d_p = c_p.rotate_left(10);
+        c_p = b_p;
+        b_p = t;
+
+        match j {
+            15 => mem::swap(&mut b, &mut b_p),
+            31 => mem::swap(&mut d, &mut d_p),
+            47 => mem::swap(&mut a, &mut a_p),
+            63 => mem::swap(&mut c, &mut c_p),
+            79 => mem::swap(&mut e, &mut e_p),
+            _ => {}
+        }
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+    state[4] = state[4].wrapping_add(e);
+    state[5] = state[5].wrapping_add(a_p);
+    state[6] = state[6].wrapping_add(b_p);
+    state[7] = state[7].wrapping_add(c_p);
+    state[8] = state[8].wrapping_add(d_p);
+    state[9] = state[9].wrapping_add(e_p);
+}
+
+/// RIPEMD-320, as specified in the AB-9601 paper.  Only as strong as
+/// RIPEMD-160: the extra width is for applications that want a longer digest.
+///
+/// Input can be fed in with any number of calls to [`Ripemd320::update`]
+/// before [`Ripemd320::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Ripemd320 {
+    state: [u32; 10],
+    buffer: BlockBuffer<64>,
+    length: u64,
+}
+
+impl Ripemd320 {
+    pub fn new() -> Ripemd320 {
+        Ripemd320 {
+            state: [
+                0x67452301,
+                0xEFCDAB89,
+                0x98BADCFE,
+                0x10325476,
+                0xC3D2E1F0,
+                0x76543210,
+                0xFEDCBA98,
+                0x89ABCDEF,
+                0x01234567,
+                0x3C2D1E0F,
+            ],
+            buffer: BlockBuffer::new(),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length = self.length.wrapping_add(input.len() as u64);
+        self.buffer.update(input, |chunk| compress(&mut self.state, chunk));
+    }
+
+    pub fn finalize(mut self) -> Output<40> {
+        // Message length in bits as a little endian u64
+        let length = self.length.wrapping_mul(8).to_le_bytes();
+        self.buffer.md_pad(&length, |chunk| compress(&mut self.state, chunk));
+
+        let mut result = [0; 40];
+        for (bytes, v) in result.chunks_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&v.to_le_bytes());
+        }
+
+        Output::new(result)
+    }
+}
+
+impl Default for Ripemd320 {
+    fn default() -> Ripemd320 {
+        Ripemd320::new()
+    }
+}
+
+impl Digest for Ripemd320 {
+    const OUTPUT_SIZE: usize = 40;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "RIPEMD-320";
+
+    type Output = Output<40>;
+
+    fn new() -> Ripemd320 {
+        Ripemd320::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Ripemd320::update(self, input)
+    }
+
+    fn finalize(self) -> Output<40> {
+        Ripemd320::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<40> {
+        ripemd320(input)
+    }
+}
+
+pub fn ripemd320(input: &[u8]) -> Output<40> {
+    let mut hasher = Ripemd320::new();
+    hasher.update(input);
+    hasher.finalize()
+}
@@ -0,0 +1,552 @@
+//! rsync's delta algorithm: bring a copy of an old file (the basis) up to
+//! date with a new one by sending only what changed.
+//!
+//! The holder of the basis sends a [`Signature`] of it: a weak rolling
+//! checksum and a strong MD4 or MD5 checksum of each block.  The holder of
+//! the new file slides a block-sized window over it a byte at a time,
+//! looking the window's rolling checksum up among the blocks' and
+//! confirming candidates with the strong checksum, and sends a [`delta`]:
+//! copies of the blocks found, and the bytes in between as they are.
+//! [`patch`] applies that to the basis to rebuild the new file.
+//!
+//! ```
+//! use std::io::Cursor;
+//!
+//! use hash_functions::rsync::{delta, patch, Signature, StrongHash};
+//!
+//! let old = b"The quick brown fox jumps over the lazy dog".repeat(100);
+//! let mut new = old.clone();
+//! new[2000..2003].copy_from_slice(b"cat");
+//!
+//! let signature = Signature::generate(&old[..], 256, StrongHash::Md5).unwrap();
+//!
+//! let mut changes = Vec::new();
+//! delta(&signature, &new[..], &mut changes).unwrap();
+//! assert!(changes.len() < 400);
+//!
+//! let mut rebuilt = Vec::new();
+//! patch(Cursor::new(&old), &changes[..], &mut rebuilt).unwrap();
+//! assert_eq!(rebuilt, new);
+//! ```
+//!
+//! # File formats
+//!
+//! Both files start with four magic bytes and a byte naming the strong
+//! hash, 4 for MD4 and 5 for MD5.  Numbers are unsigned and big-endian.
+//!
+//! A signature (magic `hsig`) goes on with the block size (4 bytes) and the
+//! length of the basis (8 bytes), then for each block in turn its weak
+//! checksum (4 bytes) and its strong one (16 bytes).  The last block is
+//! whatever is left over, and may be shorter than the rest.
+//!
+//! A delta (magic `hdlt`) goes on with a series of commands, each a byte
+//! followed by its arguments:
+//!
+//! | Byte | Arguments                | Meaning                                      |
+//! |------|--------------------------|----------------------------------------------|
+//! | 1    | offset (8), length (4)   | copy `length` bytes of the basis at `offset` |
+//! | 2    | length (4), the bytes    | output the bytes that follow                 |
+//! | 0    | strong checksum (16)     | the end; the checksum is of the new file     |
+//!
+//! [`patch`] checks the new file against the final checksum, which catches
+//! a delta applied to the wrong basis.
+
+use std::collections::HashMap;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use crate::md4::Md4;
+use crate::md5::Md5;
+use crate::stream::DEFAULT_BUFFER_SIZE;
+use crate::Output;
+
+const SIGNATURE_MAGIC: &[u8; 4] = b"hsig";
+const DELTA_MAGIC: &[u8; 4] = b"hdlt";
+
+const END: u8 = 0;
+const COPY: u8 = 1;
+const LITERAL: u8 = 2;
+
+// Literal runs are written out in pieces no longer than this, which bounds
+// how much of the new file delta() holds at once
+const MAX_LITERAL: usize = 1 << 20;
+
+/// rsync's weak checksum of a block: the sum of its bytes in the low 16
+/// bits, and the sum of their running sums in the high 16.  Cheap to slide
+/// along by a byte, dropping one from the front and adding one at the back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RollingChecksum {
+    a: u32,
+    b: u32,
+    length: u32,
+}
+
+impl RollingChecksum {
+    pub fn new(block: &[u8]) -> RollingChecksum {
+        let mut a = 0u32;
+        let mut b = 0u32;
+        for &byte in block {
+            a = a.wrapping_add(byte as u32);
+            b = b.wrapping_add(a);
+        }
+
+        RollingChecksum {
+            a,
+            b,
+            length: block.len() as u32,
+        }
+    }
+
+    /// Move the block along a byte: `out` leaves the front and `new` joins
+    /// the back.
+    pub fn roll(&mut self, out: u8, new: u8) {
+        self.a = self.a.wrapping_sub(out as u32).wrapping_add(new as u32);
+        self.b = self.b.wrapping_sub(self.length.wrapping_mul(out as u32)).wrapping_add(self.a);
+    }
+
+    /// Drop `out` from the front of the block without adding anything,
+    /// for when the window runs into the end of the input.
+    pub fn shrink(&mut self, out: u8) {
+        self.a = self.a.wrapping_sub(out as u32);
+        self.b = self.b.wrapping_sub(self.length.wrapping_mul(out as u32));
+        self.length -= 1;
+    }
+
+    pub fn value(&self) -> u32 {
+        (self.a & 0xffff) | (self.b << 16)
+    }
+}
+
+/// The strong checksum blocks are confirmed with.  rsync used MD4 up to
+/// protocol 26 and MD5 from 30.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrongHash {
+    Md4,
+    Md5,
+}
+
+impl StrongHash {
+    pub fn digest(self, input: &[u8]) -> Output<16> {
+        let mut hasher = Hasher::new(self);
+        hasher.update(input);
+        hasher.finalize()
+    }
+
+    fn id(self) -> u8 {
+        match self {
+            StrongHash::Md4 => 4,
+            StrongHash::Md5 => 5,
+        }
+    }
+
+    fn from_id(id: u8) -> io::Result<StrongHash> {
+        match id {
+            4 => Ok(StrongHash::Md4),
+            5 => Ok(StrongHash::Md5),
+            _ => Err(invalid("unknown strong hash")),
+        }
+    }
+}
+
+enum Hasher {
+    Md4(Md4),
+    Md5(Md5),
+}
+
+impl Hasher {
+    fn new(strong_hash: StrongHash) -> Hasher {
+        match strong_hash {
+            StrongHash::Md4 => Hasher::Md4(Md4::new()),
+            StrongHash::Md5 => Hasher::Md5(Md5::new()),
+        }
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        match self {
+            Hasher::Md4(hasher) => hasher.update(input),
+            Hasher::Md5(hasher) => hasher.update(input),
+        }
+    }
+
+    fn finalize(self) -> Output<16> {
+        match self {
+            Hasher::Md4(hasher) => hasher.finalize(),
+            Hasher::Md5(hasher) => hasher.finalize(),
+        }
+    }
+}
+
+/// The checksums of one block of the basis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockChecksums {
+    pub weak: u32,
+    pub strong: Output<16>,
+}
+
+/// The checksums of each block of a basis file, which is all [`delta`]
+/// needs to know about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub strong_hash: StrongHash,
+    pub block_size: u32,
+    pub basis_length: u64,
+    pub blocks: Vec<BlockChecksums>,
+}
+
+impl Signature {
+    /// Checksum `basis` in blocks of `block_size` bytes.
+    ///
+    /// Smaller blocks find more of the basis in the new file, but make for
+    /// a bigger signature.  rsync goes by the square root of the file's
+    /// length, and no less than 700 bytes.
+    ///
+    /// Panics if `block_size` is zero.
+    pub fn generate<R: Read>(mut basis: R, block_size: u32, strong_hash: StrongHash) -> io::Result<Signature> {
+        assert!(block_size > 0, "Block size must be nonzero");
+
+        let mut signature = Signature {
+            strong_hash,
+            block_size,
+            basis_length: 0,
+            blocks: Vec::new(),
+        };
+
+        let mut block = vec![0; block_size as usize];
+        loop {
+            let n = read_full(&mut basis, &mut block)?;
+            if n == 0 {
+                return Ok(signature);
+            }
+
+            signature.basis_length += n as u64;
+            signature.blocks.push(BlockChecksums {
+                weak: RollingChecksum::new(&block[..n]).value(),
+                strong: strong_hash.digest(&block[..n]),
+            });
+        }
+    }
+
+    /// Write the signature out in the format described in the [module
+    /// documentation](self).
+    pub fn write_to<W: Write>(&self, mut out: W) -> io::Result<()> {
+        out.write_all(SIGNATURE_MAGIC)?;
+        out.write_all(&[self.strong_hash.id()])?;
+        out.write_all(&self.block_size.to_be_bytes())?;
+        out.write_all(&self.basis_length.to_be_bytes())?;
+
+        for block in &self.blocks {
+            out.write_all(&block.weak.to_be_bytes())?;
+            out.write_all(block.strong.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a signature written by [`Signature::write_to`].  Anything
+    /// malformed is an error of kind [`io::ErrorKind::InvalidData`].
+    pub fn read_from<R: Read>(mut input: R) -> io::Result<Signature> {
+        if read_array(&mut input)? != *SIGNATURE_MAGIC {
+            return Err(invalid("not a signature"));
+        }
+
+        let strong_hash = StrongHash::from_id(read_array::<1>(&mut input)?[0])?;
+        let block_size = u32::from_be_bytes(read_array(&mut input)?);
+        let basis_length = u64::from_be_bytes(read_array(&mut input)?);
+        if block_size == 0 {
+            return Err(invalid("block size of zero"));
+        }
+
+        let mut blocks = Vec::new();
+        for _ in 0..basis_length.div_ceil(block_size as u64) {
+            blocks.push(BlockChecksums {
+                weak: u32::from_be_bytes(read_array(&mut input)?),
+                strong: Output::new(read_array(&mut input)?),
+            });
+        }
+
+        if input.read(&mut [0])? != 0 {
+            return Err(invalid("trailing data after the signature"));
+        }
+
+        Ok(Signature {
+            strong_hash,
+            block_size,
+            basis_length,
+            blocks,
+        })
+    }
+
+    fn block_length(&self, block: usize) -> usize {
+        let start = block as u64 * self.block_size as u64;
+        (self.basis_length - start).min(self.block_size as u64) as usize
+    }
+
+    // The block `window` is a copy of, trying `hint` first
+    fn find(&self, index: &HashMap<u32, Vec<usize>>, weak: u32, window: &[u8], hint: usize) -> Option<usize> {
+        let candidates = index.get(&weak)?;
+        let hint = Some(hint).filter(|hint| candidates.contains(hint));
+
+        let mut strong = None;
+        for block in hint.into_iter().chain(candidates.iter().copied()) {
+            if self.block_length(block) != window.len() {
+                continue;
+            }
+
+            let strong = strong.get_or_insert_with(|| self.strong_hash.digest(window));
+            if self.blocks[block].strong == *strong {
+                return Some(block);
+            }
+        }
+
+        None
+    }
+}
+
+/// Work out the changes that turn the basis `signature` was made from into
+/// `new`, and write them to `out` in the format described in the [module
+/// documentation](self).
+///
+/// `new` is read a buffer at a time, and only about a block's worth of it
+/// plus any pending literal bytes is held in memory.
+pub fn delta<R: Read, W: Write>(signature: &Signature, mut new: R, out: W) -> io::Result<()> {
+    let block_size = signature.block_size as usize;
+
+    let mut index: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (i, block) in signature.blocks.iter().enumerate() {
+        index.entry(block.weak).or_default().push(i);
+    }
+
+    let mut out = DeltaWriter::new(out, signature.strong_hash)?;
+    let mut hasher = Hasher::new(signature.strong_hash);
+
+    // buffer[start..position] is literal data yet to be written out, and
+    // the window being looked for in the basis starts at position
+    let mut buffer = Vec::new();
+    let mut start = 0;
+    let mut position = 0;
+    let mut eof = false;
+    let mut checksum: Option<RollingChecksum> = None;
+    // After one block, the next is the likeliest match
+    let mut next_block = 0;
+
+    loop {
+        // Keep a byte beyond the window where there is one, to roll in
+        if !eof && buffer.len() - position <= block_size {
+            buffer.drain(..start);
+            position -= start;
+            start = 0;
+            eof = fill(&mut new, &mut buffer, position + block_size + 1, &mut hasher)?;
+        }
+
+        let length = block_size.min(buffer.len() - position);
+        if length == 0 {
+            break;
+        }
+
+        let window = &buffer[position..position + length];
+        let rolling = checksum.get_or_insert_with(|| RollingChecksum::new(window));
+
+        if let Some(block) = signature.find(&index, rolling.value(), window, next_block) {
+            out.literal(&buffer[start..position])?;
+            out.copy(block as u64 * block_size as u64, length as u64)?;
+
+            position += length;
+            start = position;
+            checksum = None;
+            next_block = block + 1;
+            continue;
+        }
+
+        if buffer.len() - position > length {
+            rolling.roll(buffer[position], buffer[position + length]);
+        } else {
+            rolling.shrink(buffer[position]);
+        }
+        position += 1;
+
+        if position - start >= MAX_LITERAL {
+            out.literal(&buffer[start..position])?;
+            start = position;
+        }
+    }
+
+    out.literal(&buffer[start..position])?;
+    out.finish(hasher.finalize())
+}
+
+// Read into `buffer` until it holds `length` bytes, hashing what's read.
+// Returns whether the input ran out first.
+fn fill<R: Read>(input: &mut R, buffer: &mut Vec<u8>, length: usize, hasher: &mut Hasher) -> io::Result<bool> {
+    while buffer.len() < length {
+        let old_length = buffer.len();
+        buffer.resize(old_length + DEFAULT_BUFFER_SIZE, 0);
+
+        match input.read(&mut buffer[old_length..]) {
+            Ok(0) => {
+                buffer.truncate(old_length);
+                return Ok(true);
+            }
+            Ok(n) => {
+                buffer.truncate(old_length + n);
+                hasher.update(&buffer[old_length..]);
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => buffer.truncate(old_length),
+            Err(e) => {
+                buffer.truncate(old_length);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+// Writes delta commands, merging copies of consecutive blocks into one
+struct DeltaWriter<W: Write> {
+    out: W,
+    copy: Option<(u64, u64)>,
+}
+
+impl<W: Write> DeltaWriter<W> {
+    fn new(mut out: W, strong_hash: StrongHash) -> io::Result<DeltaWriter<W>> {
+        out.write_all(DELTA_MAGIC)?;
+        out.write_all(&[strong_hash.id()])?;
+        Ok(DeltaWriter { out, copy: None })
+    }
+
+    fn copy(&mut self, offset: u64, length: u64) -> io::Result<()> {
+        if let Some((start, pending)) = &mut self.copy {
+            if *start + *pending == offset && *pending + length <= u32::MAX as u64 {
+                *pending += length;
+                return Ok(());
+            }
+        }
+
+        self.flush_copy()?;
+        self.copy = Some((offset, length));
+        Ok(())
+    }
+
+    fn literal(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+
+        self.flush_copy()?;
+        self.out.write_all(&[LITERAL])?;
+        self.out.write_all(&(bytes.len() as u32).to_be_bytes())?;
+        self.out.write_all(bytes)
+    }
+
+    fn flush_copy(&mut self) -> io::Result<()> {
+        if let Some((offset, length)) = self.copy.take() {
+            self.out.write_all(&[COPY])?;
+            self.out.write_all(&offset.to_be_bytes())?;
+            self.out.write_all(&(length as u32).to_be_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self, checksum: Output<16>) -> io::Result<()> {
+        self.flush_copy()?;
+        self.out.write_all(&[END])?;
+        self.out.write_all(checksum.as_ref())?;
+        self.out.flush()
+    }
+}
+
+/// Apply `delta` to `basis`, writing the new file to `out`.
+///
+/// A malformed delta, one that copies from beyond the end of the basis, or
+/// one whose result doesn't match its checksum, is an error of kind
+/// [`io::ErrorKind::InvalidData`].  What was written to `out` by then is
+/// not to be trusted.
+pub fn patch<B: Read + Seek, R: Read, W: Write>(mut basis: B, mut delta: R, mut out: W) -> io::Result<()> {
+    if read_array(&mut delta)? != *DELTA_MAGIC {
+        return Err(invalid("not a delta"));
+    }
+
+    let strong_hash = StrongHash::from_id(read_array::<1>(&mut delta)?[0])?;
+    let mut hasher = Hasher::new(strong_hash);
+    let mut buffer = vec![0; DEFAULT_BUFFER_SIZE];
+
+    loop {
+        match read_array::<1>(&mut delta)?[0] {
+            COPY => {
+                let offset = u64::from_be_bytes(read_array(&mut delta)?);
+                let length = u32::from_be_bytes(read_array(&mut delta)?);
+
+                basis.seek(SeekFrom::Start(offset))?;
+                if transfer(&mut basis, length as u64, &mut out, &mut hasher, &mut buffer)? != length as u64 {
+                    return Err(invalid("copy from beyond the end of the basis"));
+                }
+            }
+            LITERAL => {
+                let length = u32::from_be_bytes(read_array(&mut delta)?);
+
+                if transfer(&mut delta, length as u64, &mut out, &mut hasher, &mut buffer)? != length as u64 {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+            }
+            END => {
+                let checksum = Output::new(read_array(&mut delta)?);
+                if hasher.finalize() != checksum {
+                    return Err(invalid("checksum mismatch: wrong basis, or a damaged delta"));
+                }
+                return out.flush();
+            }
+            _ => return Err(invalid("unknown delta command")),
+        }
+    }
+}
+
+// Copy up to `length` bytes from `input` to `out`, hashing them on the way,
+// and return how many there were
+fn transfer<R: Read, W: Write>(
+    input: &mut R,
+    length: u64,
+    out: &mut W,
+    hasher: &mut Hasher,
+    buffer: &mut [u8],
+) -> io::Result<u64> {
+    let mut done = 0;
+
+    while done < length {
+        let want = (length - done).min(buffer.len() as u64) as usize;
+        let n = read_full(input, &mut buffer[..want])?;
+        if n == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..n]);
+        out.write_all(&buffer[..n])?;
+        done += n as u64;
+    }
+
+    Ok(done)
+}
+
+// Fill `buffer` from `input`, short only at the end of the input
+fn read_full<R: Read>(input: &mut R, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+
+    while filled < buffer.len() {
+        match input.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(filled)
+}
+
+fn read_array<const N: usize>(input: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    input.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
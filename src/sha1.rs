@@ -0,0 +1,122 @@
+use crate::buffer::BlockBuffer;
+use crate::{Digest, Output};
+
+fn compress(state: &mut [u32; 5], chunk: &[u8; 64]) {
+    let mut w = [0u32; 80];
+    for (word, int32_bytes) in w.iter_mut().zip(chunk.chunks_exact(4)) {
+        *word = u32::from_be_bytes(int32_bytes.try_into().unwrap());
+    }
+
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1)
+    }
+
+    let mut v = *state;
+
+    rounds(&mut v, &w[0..20], 0x5A827999, |b, c, d| (b & c) | ((!b) & d));
+    rounds(&mut v, &w[20..40], 0x6ED9EBA1, |b, c, d| b ^ c ^ d);
+    rounds(&mut v, &w[40..60], 0x8F1BBCDC, |b, c, d| (b & c) | (b & d) | (c & d));
+    rounds(&mut v, &w[60..80], 0xCA62C1D6, |b, c, d| b ^ c ^ d);
+
+    for i in 0..5 {
+        state[i] = state[i].wrapping_add(v[i]);
+    }
+}
+
+// One round: twenty steps over its slice of the schedule, all with the same
+// f and k
+#[inline(always)]
+fn rounds(v: &mut [u32; 5], w: &[u32], k: u32, f: impl Fn(u32, u32, u32) -> u32) {
+    let [mut a, mut b, mut c, mut d, mut e] = *v;
+
+    for &w in w {
+        let temp = a.rotate_left(5)
+            .wrapping_add(f(b, c, d))
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(w);
+
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    *v = [a, b, c, d, e];
+}
+
+/// SHA-1, as specified in FIPS 180-4.
+///
+/// Input can be fed in with any number of calls to [`Sha1::update`] before
+/// [`Sha1::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    buffer: BlockBuffer<64>,
+    length: u64,
+}
+
+impl Sha1 {
+    pub fn new() -> Sha1 {
+        Sha1 {
+            state: [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0],
+            buffer: BlockBuffer::new(),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length = self.length.wrapping_add(input.len() as u64);
+        self.buffer.update(input, |chunk| compress(&mut self.state, chunk));
+    }
+
+    pub fn finalize(mut self) -> Output<20> {
+        // Message length in bits as a big endian u64
+        let length = self.length.wrapping_mul(8).to_be_bytes();
+        self.buffer.md_pad(&length, |chunk| compress(&mut self.state, chunk));
+
+        let mut result = [0; 20];
+        for (bytes, v) in result.chunks_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&v.to_be_bytes());
+        }
+
+        Output::new(result)
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Sha1 {
+        Sha1::new()
+    }
+}
+
+impl Digest for Sha1 {
+    const OUTPUT_SIZE: usize = 20;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "SHA-1";
+
+    type Output = Output<20>;
+
+    fn new() -> Sha1 {
+        Sha1::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha1::update(self, input)
+    }
+
+    fn finalize(self) -> Output<20> {
+        Sha1::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<20> {
+        sha1(input)
+    }
+}
+
+pub fn sha1(input: &[u8]) -> Output<20> {
+    let mut hasher = Sha1::new();
+    hasher.update(input);
+    hasher.finalize()
+}
@@ -0,0 +1,236 @@
+use crate::buffer::BlockBuffer;
+use crate::{Digest, Output};
+
+const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1,
+        0x923f82a4, 0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3,
+        0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786,
+        0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147,
+        0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13,
+        0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a,
+        0x5b9cca4f, 0x682e6ff3, 0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208,
+        0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// SHA-224 runs the same compression with its own initial values, and
+// truncates the result to seven words.
+const H224: [u32; 8] = [
+    0xc1059ed8,
+    0x367cd507,
+    0x3070dd17,
+    0xf70e5939,
+    0xffc00b31,
+    0x68581511,
+    0x64f98fa7,
+    0xbefa4fa4,
+];
+
+const H: [u32; 8] = [
+    0x6a09e667,
+    0xbb67ae85,
+    0x3c6ef372,
+    0xa54ff53a,
+    0x510e527f,
+    0x9b05688c,
+    0x1f83d9ab,
+    0x5be0cd19,
+];
+
+fn compress(state: &mut [u32; 8], chunk: &[u8; 64]) {
+    let mut w = [0u32; 64];
+    for (word, int32_bytes) in w.iter_mut().zip(chunk.chunks_exact(4)) {
+        *word = u32::from_be_bytes(int32_bytes.try_into().unwrap());
+    }
+
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut e = state[4];
+    let mut f = state[5];
+    let mut g = state[6];
+    let mut h = state[7];
+
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+    state[4] = state[4].wrapping_add(e);
+    state[5] = state[5].wrapping_add(f);
+    state[6] = state[6].wrapping_add(g);
+    state[7] = state[7].wrapping_add(h);
+}
+
+/// SHA-256, as specified in FIPS 180-4.
+///
+/// Input can be fed in with any number of calls to [`Sha256::update`] before
+/// [`Sha256::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    buffer: BlockBuffer<64>,
+    length: u64,
+}
+
+impl Sha256 {
+    pub fn new() -> Sha256 {
+        Sha256::with_initial_state(H)
+    }
+
+    fn with_initial_state(state: [u32; 8]) -> Sha256 {
+        Sha256 {
+            state,
+            buffer: BlockBuffer::new(),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length = self.length.wrapping_add(input.len() as u64);
+        self.buffer.update(input, |chunk| compress(&mut self.state, chunk));
+    }
+
+    pub fn finalize(self) -> Output<32> {
+        Output::new(self.finish())
+    }
+
+    fn finish(mut self) -> [u8; 32] {
+        // Message length in bits as a big endian u64
+        let length = self.length.wrapping_mul(8).to_be_bytes();
+        self.buffer.md_pad(&length, |chunk| compress(&mut self.state, chunk));
+
+        let mut result = [0; 32];
+        for (bytes, v) in result.chunks_mut(4).zip(&self.state) {
+            bytes.copy_from_slice(&v.to_be_bytes());
+        }
+
+        result
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Sha256 {
+        Sha256::new()
+    }
+}
+
+impl Digest for Sha256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "SHA-256";
+
+    type Output = Output<32>;
+
+    fn new() -> Sha256 {
+        Sha256::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha256::update(self, input)
+    }
+
+    fn finalize(self) -> Output<32> {
+        Sha256::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<32> {
+        sha256(input)
+    }
+}
+
+pub fn sha256(input: &[u8]) -> Output<32> {
+    let mut hasher = Sha256::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// SHA-224, as specified in FIPS 180-4: SHA-256 with a different starting
+/// state, cut down to 28 bytes.
+#[derive(Clone)]
+pub struct Sha224(Sha256);
+
+impl Sha224 {
+    pub fn new() -> Sha224 {
+        Sha224(Sha256::with_initial_state(H224))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.update(input);
+    }
+
+    pub fn finalize(self) -> Output<28> {
+        let mut result = [0; 28];
+        result.copy_from_slice(&self.0.finish()[..28]);
+        Output::new(result)
+    }
+}
+
+impl Default for Sha224 {
+    fn default() -> Sha224 {
+        Sha224::new()
+    }
+}
+
+impl Digest for Sha224 {
+    const OUTPUT_SIZE: usize = 28;
+    const BLOCK_SIZE: usize = 64;
+    const NAME: &'static str = "SHA-224";
+
+    type Output = Output<28>;
+
+    fn new() -> Sha224 {
+        Sha224::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha224::update(self, input)
+    }
+
+    fn finalize(self) -> Output<28> {
+        Sha224::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<28> {
+        sha224(input)
+    }
+}
+
+pub fn sha224(input: &[u8]) -> Output<28> {
+    let mut hasher = Sha224::new();
+    hasher.update(input);
+    hasher.finalize()
+}
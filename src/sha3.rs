@@ -0,0 +1,477 @@
+use crate::{Digest, Output};
+
+// Round constants for the iota step
+const RC: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+// Rotation offsets for the rho step, indexed by x + 5y
+const RHO: [u32; 25] = [
+    0, 1, 62, 28, 27,
+    36, 44, 6, 55, 20,
+    3, 10, 43, 25, 39,
+    41, 45, 15, 21, 8,
+    18, 2, 61, 56, 14,
+];
+
+/// The Keccak-f[1600] permutation from FIPS 202 section 3.3, over a state of
+/// 25 lanes where lane (x, y) is `a[x + 5 * y]`.
+// Rounds are numbered as in the specification, which indexes RC by them
+#[allow(clippy::needless_range_loop)]
+pub fn keccak_f1600(a: &mut [u64; 25]) {
+    for round in 0..24 {
+        // theta
+        let mut c = [0u64; 5];
+        for x in 0..5 {
+            c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+        }
+
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                a[x + 5 * y] ^= d;
+            }
+        }
+
+        // rho and pi
+        let mut b = [0u64; 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                b[y + 5 * ((2 * x + 3 * y) % 5)] = a[x + 5 * y].rotate_left(RHO[x + 5 * y]);
+            }
+        }
+
+        // chi
+        for x in 0..5 {
+            for y in 0..5 {
+                a[x + 5 * y] = b[x + 5 * y] ^ (!b[(x + 1) % 5 + 5 * y] & b[(x + 2) % 5 + 5 * y]);
+            }
+        }
+
+        // iota
+        a[0] ^= RC[round];
+    }
+}
+
+// The sponge construction: input is XORed into the first `rate` bytes of the
+// state, a block at a time, with the permutation run in between.  Once the
+// input runs out we pad, and output is read back out of the same bytes.
+//
+// `domain` holds the suffix bits that separate the functions built on the
+// same sponge (01 for SHA-3, 1111 for SHAKE) together with the first bit of
+// the pad10*1 padding.
+#[derive(Clone)]
+pub(crate) struct Sponge {
+    state: [u64; 25],
+    rate: usize,
+    pos: usize,
+    domain: u8,
+}
+
+impl Sponge {
+    pub(crate) fn new(rate: usize, domain: u8) -> Sponge {
+        Sponge {
+            state: [0; 25],
+            rate,
+            pos: 0,
+            domain,
+        }
+    }
+
+    fn xor_byte(&mut self, i: usize, b: u8) {
+        // Lanes are little endian
+        self.state[i / 8] ^= (b as u64) << ((i % 8) * 8);
+    }
+
+    fn byte(&self, i: usize) -> u8 {
+        (self.state[i / 8] >> ((i % 8) * 8)) as u8
+    }
+
+    pub(crate) fn absorb(&mut self, input: &[u8]) {
+        for &b in input {
+            self.xor_byte(self.pos, b);
+            self.pos += 1;
+
+            if self.pos == self.rate {
+                keccak_f1600(&mut self.state);
+                self.pos = 0;
+            }
+        }
+    }
+
+    // Pad the final block and switch over to squeezing.
+    pub(crate) fn pad(&mut self) {
+        self.xor_byte(self.pos, self.domain);
+        self.xor_byte(self.rate - 1, 0x80);
+        keccak_f1600(&mut self.state);
+        self.pos = 0;
+    }
+
+    pub(crate) fn squeeze(&mut self, output: &mut [u8]) {
+        for out in output {
+            if self.pos == self.rate {
+                keccak_f1600(&mut self.state);
+                self.pos = 0;
+            }
+
+            *out = self.byte(self.pos);
+            self.pos += 1;
+        }
+    }
+}
+
+const SHA3_DOMAIN: u8 = 0x06;
+const SHAKE_DOMAIN: u8 = 0x1f;
+
+/// SHA3-224, as specified in FIPS 202.
+///
+/// Input can be fed in with any number of calls to [`Sha3_224::update`] before
+/// [`Sha3_224::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Sha3_224(Sponge);
+
+impl Sha3_224 {
+    pub fn new() -> Sha3_224 {
+        Sha3_224(Sponge::new(144, SHA3_DOMAIN))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.absorb(input);
+    }
+
+    pub fn finalize(mut self) -> Output<28> {
+        let mut result = [0; 28];
+        self.0.pad();
+        self.0.squeeze(&mut result);
+        Output::new(result)
+    }
+}
+
+impl Default for Sha3_224 {
+    fn default() -> Sha3_224 {
+        Sha3_224::new()
+    }
+}
+
+impl Digest for Sha3_224 {
+    const OUTPUT_SIZE: usize = 28;
+    const BLOCK_SIZE: usize = 144;
+    const NAME: &'static str = "SHA3-224";
+
+    type Output = Output<28>;
+
+    fn new() -> Sha3_224 {
+        Sha3_224::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha3_224::update(self, input)
+    }
+
+    fn finalize(self) -> Output<28> {
+        Sha3_224::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<28> {
+        sha3_224(input)
+    }
+}
+
+pub fn sha3_224(input: &[u8]) -> Output<28> {
+    let mut hasher = Sha3_224::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// SHA3-256, as specified in FIPS 202.
+///
+/// Input can be fed in with any number of calls to [`Sha3_256::update`] before
+/// [`Sha3_256::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Sha3_256(Sponge);
+
+impl Sha3_256 {
+    pub fn new() -> Sha3_256 {
+        Sha3_256(Sponge::new(136, SHA3_DOMAIN))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.absorb(input);
+    }
+
+    pub fn finalize(mut self) -> Output<32> {
+        let mut result = [0; 32];
+        self.0.pad();
+        self.0.squeeze(&mut result);
+        Output::new(result)
+    }
+}
+
+impl Default for Sha3_256 {
+    fn default() -> Sha3_256 {
+        Sha3_256::new()
+    }
+}
+
+impl Digest for Sha3_256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 136;
+    const NAME: &'static str = "SHA3-256";
+
+    type Output = Output<32>;
+
+    fn new() -> Sha3_256 {
+        Sha3_256::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha3_256::update(self, input)
+    }
+
+    fn finalize(self) -> Output<32> {
+        Sha3_256::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<32> {
+        sha3_256(input)
+    }
+}
+
+pub fn sha3_256(input: &[u8]) -> Output<32> {
+    let mut hasher = Sha3_256::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// SHA3-384, as specified in FIPS 202.
+///
+/// Input can be fed in with any number of calls to [`Sha3_384::update`] before
+/// [`Sha3_384::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Sha3_384(Sponge);
+
+impl Sha3_384 {
+    pub fn new() -> Sha3_384 {
+        Sha3_384(Sponge::new(104, SHA3_DOMAIN))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.absorb(input);
+    }
+
+    pub fn finalize(mut self) -> Output<48> {
+        let mut result = [0; 48];
+        self.0.pad();
+        self.0.squeeze(&mut result);
+        Output::new(result)
+    }
+}
+
+impl Default for Sha3_384 {
+    fn default() -> Sha3_384 {
+        Sha3_384::new()
+    }
+}
+
+impl Digest for Sha3_384 {
+    const OUTPUT_SIZE: usize = 48;
+    const BLOCK_SIZE: usize = 104;
+    const NAME: &'static str = "SHA3-384";
+
+    type Output = Output<48>;
+
+    fn new() -> Sha3_384 {
+        Sha3_384::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha3_384::update(self, input)
+    }
+
+    fn finalize(self) -> Output<48> {
+        Sha3_384::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<48> {
+        sha3_384(input)
+    }
+}
+
+pub fn sha3_384(input: &[u8]) -> Output<48> {
+    let mut hasher = Sha3_384::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// SHA3-512, as specified in FIPS 202.
+///
+/// Input can be fed in with any number of calls to [`Sha3_512::update`] before
+/// [`Sha3_512::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Sha3_512(Sponge);
+
+impl Sha3_512 {
+    pub fn new() -> Sha3_512 {
+        Sha3_512(Sponge::new(72, SHA3_DOMAIN))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.absorb(input);
+    }
+
+    pub fn finalize(mut self) -> Output<64> {
+        let mut result = [0; 64];
+        self.0.pad();
+        self.0.squeeze(&mut result);
+        Output::new(result)
+    }
+}
+
+impl Default for Sha3_512 {
+    fn default() -> Sha3_512 {
+        Sha3_512::new()
+    }
+}
+
+impl Digest for Sha3_512 {
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 72;
+    const NAME: &'static str = "SHA3-512";
+
+    type Output = Output<64>;
+
+    fn new() -> Sha3_512 {
+        Sha3_512::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha3_512::update(self, input)
+    }
+
+    fn finalize(self) -> Output<64> {
+        Sha3_512::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<64> {
+        sha3_512(input)
+    }
+}
+
+pub fn sha3_512(input: &[u8]) -> Output<64> {
+    let mut hasher = Sha3_512::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// The output of a SHAKE function.  Successive reads carry on where the last
+/// one stopped, so reading 10 bytes and then 22 gives the same 32 bytes as
+/// reading them all at once.
+///
+/// Unlike BLAKE3's [`OutputReader`](crate::blake3::OutputReader), there's no
+/// seeking: each block of output is the permutation of the one before.
+#[derive(Clone)]
+pub struct XofReader {
+    sponge: Sponge,
+    position: u64,
+}
+
+impl XofReader {
+    pub fn read(&mut self, output: &mut [u8]) {
+        self.sponge.squeeze(output);
+        self.position += output.len() as u64;
+    }
+
+    /// How many bytes have been read so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+/// SHAKE128, the extendable-output function from FIPS 202 with 128-bit
+/// security.
+///
+/// After feeding in the input, [`Shake128::finalize_xof`] gives a reader from
+/// which any amount of output can be pulled.
+#[derive(Clone)]
+pub struct Shake128(Sponge);
+
+impl Shake128 {
+    pub fn new() -> Shake128 {
+        Shake128(Sponge::new(168, SHAKE_DOMAIN))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.absorb(input);
+    }
+
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.0.pad();
+        XofReader {
+            sponge: self.0,
+            position: 0,
+        }
+    }
+}
+
+impl Default for Shake128 {
+    fn default() -> Shake128 {
+        Shake128::new()
+    }
+}
+
+/// The first `length` bytes of SHAKE128 output for `input`.
+pub fn shake128(input: &[u8], length: usize) -> Vec<u8> {
+    let mut hasher = Shake128::new();
+    hasher.update(input);
+
+    let mut result = vec![0; length];
+    hasher.finalize_xof().read(&mut result);
+    result
+}
+
+/// SHAKE256, the extendable-output function from FIPS 202 with 256-bit
+/// security.
+///
+/// After feeding in the input, [`Shake256::finalize_xof`] gives a reader from
+/// which any amount of output can be pulled.
+#[derive(Clone)]
+pub struct Shake256(Sponge);
+
+impl Shake256 {
+    pub fn new() -> Shake256 {
+        Shake256(Sponge::new(136, SHAKE_DOMAIN))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.absorb(input);
+    }
+
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.0.pad();
+        XofReader {
+            sponge: self.0,
+            position: 0,
+        }
+    }
+}
+
+impl Default for Shake256 {
+    fn default() -> Shake256 {
+        Shake256::new()
+    }
+}
+
+/// The first `length` bytes of SHAKE256 output for `input`.
+pub fn shake256(input: &[u8], length: usize) -> Vec<u8> {
+    let mut hasher = Shake256::new();
+    hasher.update(input);
+
+    let mut result = vec![0; length];
+    hasher.finalize_xof().read(&mut result);
+    result
+}
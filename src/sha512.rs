@@ -0,0 +1,460 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::buffer::BlockBuffer;
+use crate::{Digest, Output, VarOutput};
+
+const K: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc, 0x3956c25bf348b538, 0x59f111f1b605d019,
+    0x923f82a4af194f9b, 0xab1c5ed5da6d8118, 0xd807aa98a3030242,
+    0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235,
+    0xc19bf174cf692694, 0xe49b69c19ef14ad2, 0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65, 0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f,
+    0xbf597fc7beef0ee4, 0xc6e00bf33da88fc2, 0xd5a79147930aa725,
+    0x06ca6351e003826f, 0x142929670a0e6e70, 0x27b70a8546d22ffc,
+    0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+    0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6,
+    0x92722c851482353b, 0xa2bfe8a14cf10364, 0xa81a664bbc423001,
+    0xc24b8b70d0f89791, 0xc76c51a30654be30, 0xd192e819d6ef5218,
+    0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8, 0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3, 0x748f82ee5defb2fc,
+    0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915,
+    0xc67178f2e372532b, 0xca273eceea26619c, 0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178, 0x06f067aa72176fba,
+    0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c, 0x4cc5d4becb3e42b6, 0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+ ];
+
+// The truncated variants each start from their own initial values.  For
+// SHA-512/224 and SHA-512/256 these are the ones FIPS 180-4 section 5.3.6
+// derives with `sha512_t_iv`, written out.
+const H384: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+const H512_224: [u64; 8] = [
+    0x8c3d37c819544da2,
+    0x73e1996689dcd4d6,
+    0x1dfab7ae32ff9c82,
+    0x679dd514582f9fcf,
+    0x0f6d2b697bd44da8,
+    0x77e36f7304c48942,
+    0x3f9d85a86a1d36c8,
+    0x1112e6ad91d692a1,
+];
+
+const H512_256: [u64; 8] = [
+    0x22312194fc2bf72c,
+    0x9f555fa3c84c64c2,
+    0x2393b86b6f53b151,
+    0x963877195940eabd,
+    0x96283ee2a88effe3,
+    0xbe5e1e2553863992,
+    0x2b0199fc2c85b8aa,
+    0x0eb72ddc81c52ca2,
+];
+
+const H: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+fn compress(state: &mut [u64; 8], chunk: &[u8; 128]) {
+    let mut w = [0u64; 80];
+    for (word, int64_bytes) in w.iter_mut().zip(chunk.chunks_exact(8)) {
+        *word = u64::from_be_bytes(int64_bytes.try_into().unwrap());
+    }
+
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut e = state[4];
+    let mut f = state[5];
+    let mut g = state[6];
+    let mut h = state[7];
+
+    for i in 0..80 {
+        let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+    state[4] = state[4].wrapping_add(e);
+    state[5] = state[5].wrapping_add(f);
+    state[6] = state[6].wrapping_add(g);
+    state[7] = state[7].wrapping_add(h);
+}
+
+/// SHA-512, as specified in FIPS 180-4.
+///
+/// Input can be fed in with any number of calls to [`Sha512::update`] before
+/// [`Sha512::finalize`] pads the message and produces the digest.
+#[derive(Clone)]
+pub struct Sha512 {
+    state: [u64; 8],
+    buffer: BlockBuffer<128>,
+    length: u128,
+}
+
+impl Sha512 {
+    pub fn new() -> Sha512 {
+        Sha512::with_initial_state(H)
+    }
+
+    fn with_initial_state(state: [u64; 8]) -> Sha512 {
+        Sha512 {
+            state,
+            buffer: BlockBuffer::new(),
+            length: 0,
+        }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length = self.length.wrapping_add(input.len() as u128);
+        self.buffer.update(input, |chunk| compress(&mut self.state, chunk));
+    }
+
+    pub fn finalize(self) -> Output<64> {
+        Output::new(self.finish())
+    }
+
+    fn finish(mut self) -> [u8; 64] {
+        // Message length in bits as a big endian u128
+        let length = self.length.wrapping_mul(8).to_be_bytes();
+        self.buffer.md_pad(&length, |chunk| compress(&mut self.state, chunk));
+
+        let mut result = [0; 64];
+        for (bytes, v) in result.chunks_mut(8).zip(&self.state) {
+            bytes.copy_from_slice(&v.to_be_bytes());
+        }
+
+        result
+    }
+}
+
+impl Default for Sha512 {
+    fn default() -> Sha512 {
+        Sha512::new()
+    }
+}
+
+impl Digest for Sha512 {
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 128;
+    const NAME: &'static str = "SHA-512";
+
+    type Output = Output<64>;
+
+    fn new() -> Sha512 {
+        Sha512::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha512::update(self, input)
+    }
+
+    fn finalize(self) -> Output<64> {
+        Sha512::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<64> {
+        sha512(input)
+    }
+}
+
+pub fn sha512(input: &[u8]) -> Output<64> {
+    let mut hasher = Sha512::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// SHA-384, as specified in FIPS 180-4: SHA-512 with a different starting
+/// state, cut down to 48 bytes.
+#[derive(Clone)]
+pub struct Sha384(Sha512);
+
+impl Sha384 {
+    pub fn new() -> Sha384 {
+        Sha384(Sha512::with_initial_state(H384))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.update(input);
+    }
+
+    pub fn finalize(self) -> Output<48> {
+        let mut result = [0; 48];
+        result.copy_from_slice(&self.0.finish()[..48]);
+        Output::new(result)
+    }
+}
+
+impl Default for Sha384 {
+    fn default() -> Sha384 {
+        Sha384::new()
+    }
+}
+
+impl Digest for Sha384 {
+    const OUTPUT_SIZE: usize = 48;
+    const BLOCK_SIZE: usize = 128;
+    const NAME: &'static str = "SHA-384";
+
+    type Output = Output<48>;
+
+    fn new() -> Sha384 {
+        Sha384::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha384::update(self, input)
+    }
+
+    fn finalize(self) -> Output<48> {
+        Sha384::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<48> {
+        sha384(input)
+    }
+}
+
+pub fn sha384(input: &[u8]) -> Output<48> {
+    let mut hasher = Sha384::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// SHA-512/224, as specified in FIPS 180-4.
+#[derive(Clone)]
+pub struct Sha512_224(Sha512);
+
+impl Sha512_224 {
+    pub fn new() -> Sha512_224 {
+        Sha512_224(Sha512::with_initial_state(H512_224))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.update(input);
+    }
+
+    pub fn finalize(self) -> Output<28> {
+        let mut result = [0; 28];
+        result.copy_from_slice(&self.0.finish()[..28]);
+        Output::new(result)
+    }
+}
+
+impl Default for Sha512_224 {
+    fn default() -> Sha512_224 {
+        Sha512_224::new()
+    }
+}
+
+impl Digest for Sha512_224 {
+    const OUTPUT_SIZE: usize = 28;
+    const BLOCK_SIZE: usize = 128;
+    const NAME: &'static str = "SHA-512/224";
+
+    type Output = Output<28>;
+
+    fn new() -> Sha512_224 {
+        Sha512_224::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha512_224::update(self, input)
+    }
+
+    fn finalize(self) -> Output<28> {
+        Sha512_224::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<28> {
+        sha512_224(input)
+    }
+}
+
+pub fn sha512_224(input: &[u8]) -> Output<28> {
+    let mut hasher = Sha512_224::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// SHA-512/256, as specified in FIPS 180-4.
+#[derive(Clone)]
+pub struct Sha512_256(Sha512);
+
+impl Sha512_256 {
+    pub fn new() -> Sha512_256 {
+        Sha512_256(Sha512::with_initial_state(H512_256))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.update(input);
+    }
+
+    pub fn finalize(self) -> Output<32> {
+        let mut result = [0; 32];
+        result.copy_from_slice(&self.0.finish()[..32]);
+        Output::new(result)
+    }
+}
+
+impl Default for Sha512_256 {
+    fn default() -> Sha512_256 {
+        Sha512_256::new()
+    }
+}
+
+impl Digest for Sha512_256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 128;
+    const NAME: &'static str = "SHA-512/256";
+
+    type Output = Output<32>;
+
+    fn new() -> Sha512_256 {
+        Sha512_256::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha512_256::update(self, input)
+    }
+
+    fn finalize(self) -> Output<32> {
+        Sha512_256::finalize(self)
+    }
+
+    fn digest(input: &[u8]) -> Output<32> {
+        sha512_256(input)
+    }
+}
+
+pub fn sha512_256(input: &[u8]) -> Output<32> {
+    let mut hasher = Sha512_256::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// A truncation length that SHA-512/t doesn't allow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTruncation(pub usize);
+
+impl fmt::Display for InvalidTruncation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SHA-512/t needs t to be a multiple of 8 below 512 (and not 384), not {}",
+            self.0
+        )
+    }
+}
+
+impl Error for InvalidTruncation {}
+
+/// Initial values for SHA-512/t, per FIPS 180-4 section 5.3.6: SHA-512 of the
+/// string "SHA-512/t", run from SHA-512's initial values each XORed with
+/// a5a5a5a5a5a5a5a5.
+///
+/// `t` has to be a multiple of 8 below 512.  384 is excluded because the
+/// standard already has SHA-384, which starts somewhere else entirely.
+pub fn sha512_t_iv(t: usize) -> Result<[u64; 8], InvalidTruncation> {
+    if t == 0 || t >= 512 || !t.is_multiple_of(8) || t == 384 {
+        return Err(InvalidTruncation(t));
+    }
+
+    let mut generator = Sha512::with_initial_state(H.map(|h| h ^ 0xa5a5a5a5a5a5a5a5));
+    generator.update(format!("SHA-512/{}", t).as_bytes());
+
+    let mut iv = [0u64; 8];
+    for (word, bytes) in iv.iter_mut().zip(generator.finish().chunks(8)) {
+        *word = u64::from_be_bytes(bytes.try_into().unwrap());
+    }
+
+    Ok(iv)
+}
+
+/// SHA-512/t for any permitted `t`, as specified in FIPS 180-4.  For the two
+/// approved lengths, [`Sha512_224`] and [`Sha512_256`] avoid deriving the
+/// initial values each time.
+#[derive(Clone)]
+pub struct Sha512T {
+    inner: Sha512,
+    t: usize,
+}
+
+impl Sha512T {
+    pub fn new(t: usize) -> Result<Sha512T, InvalidTruncation> {
+        Ok(Sha512T {
+            inner: Sha512::with_initial_state(sha512_t_iv(t)?),
+            t,
+        })
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.inner.update(input);
+    }
+
+    pub fn finalize(self) -> VarOutput<64> {
+        VarOutput::new(&self.inner.finish()[..self.t / 8])
+    }
+}
+
+pub fn sha512_t(input: &[u8], t: usize) -> Result<VarOutput<64>, InvalidTruncation> {
+    let mut hasher = Sha512T::new(t)?;
+    hasher.update(input);
+    Ok(hasher.finalize())
+}
@@ -0,0 +1,52 @@
+//! Hashing files and other readers without holding them in memory.
+//!
+//! Input goes through a single buffer of fixed size, so a 20 GB disk image
+//! takes no more memory to hash than an empty file.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::Digest;
+
+/// Buffer size used by [`digest_file`], and a reasonable choice for
+/// [`digest_reader`]: big enough that the time goes on hashing rather than
+/// system calls.
+pub const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Read `reader` to the end, `buffer_size` bytes at a time, passing each
+/// piece read to `update`.
+///
+/// Panics if `buffer_size` is zero.
+pub fn read_chunks<R: Read>(
+    mut reader: R,
+    buffer_size: usize,
+    mut update: impl FnMut(&[u8]),
+) -> io::Result<()> {
+    assert!(buffer_size > 0, "Buffer size must be nonzero");
+
+    let mut buffer = vec![0; buffer_size];
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => update(&buffer[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Hash everything `reader` produces, reading `buffer_size` bytes at a time.
+///
+/// Panics if `buffer_size` is zero.
+pub fn digest_reader<D: Digest, R: Read>(reader: R, buffer_size: usize) -> io::Result<D::Output> {
+    let mut hasher = D::new();
+    read_chunks(reader, buffer_size, |chunk| hasher.update(chunk))?;
+    Ok(hasher.finalize())
+}
+
+/// Hash the contents of the file at `path`.
+pub fn digest_file<D: Digest, P: AsRef<Path>>(path: P) -> io::Result<D::Output> {
+    digest_reader::<D, _>(File::open(path)?, DEFAULT_BUFFER_SIZE)
+}
@@ -10,15 +10,16 @@ make
 
 temp=$(mktemp)
 iterations=1000
+bindir=target/release
 
-binaries=(ripemd160 md4 md5 sha1 sha256 sha512 blake2)
-system_equivs=("openssl dgst -r -ripemd160" "openssl dgst -r -md4" md5sum sha1sum sha256sum sha512sum b2sum)
+binaries=(ripemd160 md4 md5 sha1 sha224 sha256 sha384 sha512 sha512_224 sha512_256 sha3_224 sha3_256 sha3_384 sha3_512 blake2 blake2s)
+system_equivs=("openssl dgst -r -ripemd160" "openssl dgst -r -md4" md5sum sha1sum sha224sum sha256sum sha384sum sha512sum "openssl dgst -r -sha512-224" "openssl dgst -r -sha512-256" "openssl dgst -r -sha3-224" "openssl dgst -r -sha3-256" "openssl dgst -r -sha3-384" "openssl dgst -r -sha3-512" b2sum "openssl dgst -r -blake2s256")
 
 for i in ${!binaries[@]}; do
     binary=${binaries[i]}
     system_equiv=${system_equivs[i]}
 
-    if [ ! -e "${binary}" ] || [ "`which $system_equiv`" = "" ]; then
+    if [ ! -e "${bindir}/${binary}" ] || [ "`which $system_equiv`" = "" ]; then
         echo "Missing dependency.  Test can't continue!"
         exit
     fi
@@ -27,7 +28,7 @@ for i in ${!binaries[@]}; do
 
     result=$(
         for i in `seq 0 $iterations`; do
-            head -c $i /dev/urandom > "$temp"; ./${binary} "$temp" | awk '{print $2}'; ${system_equiv} "$temp";
+            head -c $i /dev/urandom > "$temp"; "${bindir}/${binary}" "$temp"; ${system_equiv} "$temp";
         done | awk '{print $1}' | uniq -u
           )
 
@@ -0,0 +1,224 @@
+use hash_functions::argon2::{InvalidParameter, Params, ParsePasswordHashError, PasswordHash, Variant};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// RFC 9106 section 5: every input set, with four lanes over 32 KiB
+fn rfc9106(variant: Variant) -> Result<String, InvalidParameter> {
+    let params = Params::new(variant)
+        .memory_cost(32)
+        .time_cost(3)?
+        .parallelism(4)?
+        .secret(&[0x03; 8])
+        .associated_data(&[0x04; 12]);
+
+    Ok(hex(&params.hash(&[0x01; 32], &[0x02; 16])?))
+}
+
+#[test]
+fn rfc9106_argon2d() -> Result<(), InvalidParameter> {
+    assert_eq!(
+        rfc9106(Variant::Argon2d)?,
+        "512b391b6f1162975371d30919734294f868e3be3984f3c1a13a4db9fabe4acb"
+    );
+    Ok(())
+}
+
+#[test]
+fn rfc9106_argon2i() -> Result<(), InvalidParameter> {
+    assert_eq!(
+        rfc9106(Variant::Argon2i)?,
+        "c814d9d1dc7f37aa13f0d77f2494bda1c8de6b016dd388d29952a4c4672b6ce8"
+    );
+    Ok(())
+}
+
+#[test]
+fn rfc9106_argon2id() -> Result<(), InvalidParameter> {
+    assert_eq!(
+        rfc9106(Variant::Argon2id)?,
+        "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659"
+    );
+    Ok(())
+}
+
+// Against OpenSSL's `kdf ARGON2*`: memory that isn't a multiple of four
+// blocks per lane, tags longer than one BLAKE2b output, and segments long
+// enough to need several blocks of Argon2i addresses.
+#[test]
+fn other_shapes() -> Result<(), InvalidParameter> {
+    let cases = [
+        (Variant::Argon2id, 65, 2, 1, 32, "8dcfab1f14f631aaee90213bb64a13c45fc1f9cd425e2293357912e5df225bb6"),
+        (
+            Variant::Argon2i,
+            37,
+            1,
+            3,
+            100,
+            "83726e33ec00976833fef9ca8f1a80dc1627c1f41043481bd47ca36e2f75567027891ac17a3514988802b7bee83c0fbc\
+             8d369b35f15c5aa69e03ba36645e225e3aee9f6bad2a576dd170c38ed4bd8a08d13408626a316093bf7dac1734abbefb\
+             5b69ac0c",
+        ),
+        (Variant::Argon2d, 256, 4, 2, 16, "f86b345166383c701a9f613b36b67a56"),
+        (Variant::Argon2i, 64, 3, 1, 32, "8a1a14bea56ca2fe8ec989795f1d13caf25903d02d8fa9e451b5d3463d8d3331"),
+    ];
+
+    for (variant, memory, time, lanes, length, expected) in cases {
+        let params = Params::new(variant)
+            .memory_cost(memory)
+            .time_cost(time)?
+            .parallelism(lanes)?
+            .output_length(length)?;
+        assert_eq!(hex(&params.hash(b"password", b"somesalt")?), expected, "{:?}", variant);
+    }
+
+    let cases = [
+        (Variant::Argon2i, 2048, 2, 1, "7b6dbbab9127ba3e1f2b58b9134b10e6e909379881fee9036fa2282b5d8ae5a7"),
+        (Variant::Argon2id, 1024, 2, 2, "f269189f8aad2b91c726d41023e14d40a13850409f91be5d883e7ad3fcbb999b"),
+    ];
+
+    for (variant, memory, time, lanes, expected) in cases {
+        let params = Params::new(variant).memory_cost(memory).time_cost(time)?.parallelism(lanes)?;
+        assert_eq!(hex(&params.hash(b"password", b"somesaltsalt")?), expected, "{:?}", variant);
+    }
+    Ok(())
+}
+
+#[test]
+fn threads_do_not_change_the_hash() -> Result<(), InvalidParameter> {
+    let expected = "5e9232fc3fe820a324bba9f8835db92832ab3b3de509c4bcc569723e71f2016d\
+                    fcf6b057d9ddbc6d46b796761238da84f5097b6622bc294b64ea5c6825e4b6bb";
+
+    let params = Params::new(Variant::Argon2d).memory_cost(2048).time_cost(1)?.parallelism(8)?.output_length(64)?;
+
+    for threads in [1, 3, 8] {
+        let params = params.clone().threads(threads)?;
+        assert_eq!(hex(&params.hash(b"password", b"somesaltsalt")?), expected, "{} threads", threads);
+    }
+    Ok(())
+}
+
+// The reference implementation's own test of its encoding
+#[test]
+fn phc_string() -> Result<(), InvalidParameter> {
+    let params = Params::new(Variant::Argon2id).memory_cost(65536).time_cost(2)?.parallelism(1)?;
+    let encoded = params.hash_password(b"password", b"somesalt")?.to_string();
+    assert_eq!(
+        encoded,
+        "$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMaO6Mm5c8y7cJHAph8ArZWb2GRPPc"
+    );
+
+    let hash: PasswordHash = encoded.parse().unwrap();
+    assert!(hash.verify(b"password"));
+    assert!(!hash.verify(b"passwore"));
+    assert_eq!(hash.to_string(), encoded);
+    Ok(())
+}
+
+#[test]
+fn phc_string_with_secret_and_data() -> Result<(), InvalidParameter> {
+    let params = Params::new(Variant::Argon2i)
+        .memory_cost(64)
+        .time_cost(1)?
+        .parallelism(2)?
+        .secret(b"pepper")
+        .associated_data(b"user 42");
+    let encoded = params.hash_password(b"password", b"saltsaltsalt")?.to_string();
+    assert!(encoded.starts_with("$argon2i$v=19$m=64,t=1,p=2,data=dXNlciA0Mg$c2FsdHNhbHRzYWx0$"));
+
+    // The secret isn't in the string, so has to be supplied again
+    let mut hash: PasswordHash = encoded.parse().unwrap();
+    assert!(!hash.verify(b"password"));
+    hash.params = hash.params.secret(b"pepper");
+    assert!(hash.verify(b"password"));
+    Ok(())
+}
+
+#[test]
+fn bad_phc_strings() {
+    use ParsePasswordHashError::*;
+
+    let cases = [
+        ("argon2id$v=19$m=64,t=1,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Format),
+        ("$argon2id$v=19$m=64,t=1,p=1$c29tZXNhbHQ", Format),
+        ("$scrypt$v=19$m=64,t=1,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Algorithm),
+        ("$argon2id$v=16$m=64,t=1,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Version),
+        ("$argon2id$m=64,t=1,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Format),
+        ("$argon2id$v=19$m=64,t=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=64,t=1,p=1,t=2$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=64,t=1,p=1,x=2$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=64,t=0,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=7,t=1,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=064,t=1,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        // Beyond the limits on what a stored hash may ask for
+        ("$argon2id$v=19$m=2097153,t=1,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=4294967295,t=1,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=64,t=1025,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=4096,t=1,p=256$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=64,t=1,p=16777215$c29tZXNhbHQ$CTFhFdXPJO1aFaMa", Parameters),
+        ("$argon2id$v=19$m=64,t=1,p=1$c29tZXNhbHQ=$CTFhFdXPJO1aFaMa", Encoding),
+        ("$argon2id$v=19$m=64,t=1,p=1$c29tZXNhbHR$CTFhFdXPJO1aFaMa", Encoding),
+        ("$argon2id$v=19$m=64,t=1,p=1$c29tZXNhbA$CTFhFdXPJO1aFaMa", Length),
+        ("$argon2id$v=19$m=64,t=1,p=1$c29tZXNhbHQ$CTFh", Length),
+    ];
+
+    for (s, error) in cases {
+        assert_eq!(s.parse::<PasswordHash>().err(), Some(error), "{}", s);
+    }
+}
+
+#[test]
+fn largest_parsed_parameters() {
+    let hash: PasswordHash = "$argon2id$v=19$m=2097152,t=1024,p=255$c29tZXNhbHQ$CTFhFdXPJO1aFaMa"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        hash.to_string(),
+        "$argon2id$v=19$m=2097152,t=1024,p=255$c29tZXNhbHQ$CTFhFdXPJO1aFaMa"
+    );
+}
+
+#[test]
+fn parameters_out_of_range() {
+    use InvalidParameter::*;
+
+    let params = Params::new(Variant::Argon2id);
+    assert_eq!(params.clone().time_cost(0).err(), Some(TimeCost));
+    assert_eq!(params.clone().parallelism(0).err(), Some(Parallelism(0)));
+    assert_eq!(params.clone().parallelism(1 << 24).err(), Some(Parallelism(1 << 24)));
+    assert_eq!(params.clone().threads(0).err(), Some(Threads));
+    assert_eq!(params.clone().output_length(3).err(), Some(OutputLength(3)));
+    assert!(params.clone().parallelism((1 << 24) - 1).is_ok());
+    assert!(params.clone().output_length(4).is_ok());
+
+    // The salt and the memory cost are only checked when hashing
+    let params = params.memory_cost(64);
+    assert_eq!(params.hash(b"password", b"salt").err(), Some(SaltLength(4)));
+    assert_eq!(params.hash_password(b"password", b"1234567").err(), Some(SaltLength(7)));
+
+    let params = params.memory_cost(31);
+    assert_eq!(params.hash(b"password", b"somesalt").err(), Some(MemoryCost(31)));
+    assert_eq!(params.hash_password(b"password", b"somesalt").err(), Some(MemoryCost(31)));
+}
+
+// Fields set by hand to parameters that can't be hashed with never verify
+#[test]
+fn unusable_fields() -> Result<(), InvalidParameter> {
+    let params = Params::new(Variant::Argon2id).memory_cost(64).time_cost(1)?.parallelism(1)?;
+    let hash = params.hash_password(b"password", b"somesalt")?;
+    assert!(hash.verify(b"password"));
+
+    let mut short_salt = hash.clone();
+    short_salt.salt.truncate(4);
+    assert!(!short_salt.verify(b"password"));
+
+    let mut short_hash = hash.clone();
+    short_hash.hash.truncate(3);
+    assert!(!short_hash.verify(b"password"));
+
+    let mut too_little_memory = hash;
+    too_little_memory.params = too_little_memory.params.memory_cost(4);
+    assert!(!too_little_memory.verify(b"password"));
+    Ok(())
+}
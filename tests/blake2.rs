@@ -0,0 +1,195 @@
+use hash_functions::blake2::{blake2, blake2s, Blake2b, Blake2s, InvalidParameter, Params};
+use hash_functions::Digest;
+
+// From blake2b-kat.txt in the BLAKE2 reference repository: the key is the
+// bytes 0x00..0x3f, and the input for entry n is the bytes 0x00..n-1.
+const KEYED_KAT: &[(usize, &str)] = &[
+    (0, "10ebb67700b1868efb4417987acf4690ae9d972fb7a590c2f02871799aaa4786b5e996e8f0f4eb981fc214b005f42d2ff4233499391653df7aefcbc13fc51568"),
+    (1, "961f6dd1e4dd30f63901690c512e78e4b45e4742ed197c3c5e45c549fd25f2e4187b0bc9fe30492b16b0d0bc4ef9b0f34c7003fac09a5ef1532e69430234cebd"),
+    (2, "da2cfbe2d8409a0f38026113884f84b50156371ae304c4430173d08a99d9fb1b983164a3770706d537f49e0c916d9f32b95cc37a95b99d857436f0232c88a965"),
+    (127, "76d2d819c92bce55fa8e092ab1bf9b9eab237a25267986cacf2b8ee14d214d730dc9a5aa2d7b596e86a1fd8fa0804c77402d2fcd45083688b218b1cdfa0dcbcb"),
+    (128, "72065ee4dd91c2d8509fa1fc28a37c7fc9fa7d5b3f8ad3d0d7a25626b57b1b44788d4caf806290425f9890a3a2a35a905ab4b37acfd0da6e4517b2525c9651e4"),
+    (129, "64475dfe7600d7171bea0b394e27c9b00d8e74dd1e416a79473682ad3dfdbb706631558055cfc8a40e07bd015a4540dcdea15883cbbf31412df1de1cd4152b91"),
+    (255, "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e92484be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461"),
+];
+
+// Likewise from blake2s-kat.txt, with the key 0x00..0x1f.
+const KEYED_KAT_S: &[(usize, &str)] = &[
+    (0, "48a8997da407876b3d79c0d92325ad3b89cbb754d86ab71aee047ad345fd2c49"),
+    (1, "40d15fee7c328830166ac3f918650f807e7e01e177258cdc0a39b11f598066f1"),
+    (63, "c65382513f07460da39833cb666c5ed82e61b9e998f4b0c4287cee56c3cc9bcd"),
+    (64, "8975b0577fd35566d750b362b0897a26c399136df07bababbde6203ff2954ed4"),
+    (65, "21fe0ceb0052be7fb0f004187cacd7de67fa6eb0938d927677f2398c132317a8"),
+    (255, "3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd"),
+];
+
+fn sequence(n: usize) -> Vec<u8> {
+    (0..n).map(|i| i as u8).collect()
+}
+
+#[test]
+fn rfc7693_abc() {
+    assert_eq!(
+        blake2(b"abc", None, 64).unwrap().to_string(),
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+    );
+}
+
+#[test]
+fn rfc7693_abc_blake2s() {
+    assert_eq!(
+        blake2s(b"abc", None, 32).unwrap().to_string(),
+        "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+    );
+}
+
+#[test]
+fn keyed_kat() {
+    let key = sequence(64);
+
+    for &(n, expected) in KEYED_KAT {
+        let input = sequence(n);
+        assert_eq!(blake2(&input, Some(&key), 64).unwrap().to_string(), expected, "entry {}", n);
+
+        // Same again, a byte at a time
+        let mut state = Params::new().key(&key).unwrap().to_state();
+        for b in &input {
+            state.update(&[*b]);
+        }
+        assert_eq!(state.finalize().to_string(), expected, "entry {}", n);
+    }
+}
+
+#[test]
+fn keyed_kat_blake2s() {
+    let key = sequence(32);
+
+    for &(n, expected) in KEYED_KAT_S {
+        let input = sequence(n);
+        assert_eq!(blake2s(&input, Some(&key), 32).unwrap().to_string(), expected, "entry {}", n);
+
+        let mut state = Blake2s::new_keyed(&key, 32).unwrap();
+        for b in &input {
+            state.update(&[*b]);
+        }
+        assert_eq!(state.finalize().to_string(), expected, "entry {}", n);
+    }
+}
+
+// The remaining vectors come from the reference implementation as exposed by
+// Python's hashlib.blake2b, which accepts the full parameter block.
+
+#[test]
+fn salt_and_personalization() -> Result<(), InvalidParameter> {
+    let digest = Params::new()
+        .digest_length(32)?
+        .salt(b"0123456789abcdef")?
+        .personal(b"my-app v1")?
+        .hash(b"hello");
+
+    assert_eq!(
+        digest.to_string(),
+        "f5c319f505caeef37b98359b29f12a8ccd36317486c62215e4a78f3f15b44345"
+    );
+
+    let digest = Params::new()
+        .digest_length(1)?
+        .salt(b"ab")?
+        .personal(b"cd")?
+        .hash(b"");
+
+    assert_eq!(digest.to_string(), "29");
+
+    Ok(())
+}
+
+#[test]
+fn tree_parameters() -> Result<(), InvalidParameter> {
+    let digest = Params::new()
+        .digest_length(48)?
+        .key(b"secret")?
+        .fanout(2)
+        .max_depth(3)?
+        .max_leaf_length(4096)
+        .node_offset(0x0102030405)
+        .node_depth(1)
+        .inner_hash_length(64)?
+        .last_node(true)
+        .hash(&sequence(200));
+
+    assert_eq!(
+        digest.to_string(),
+        "4c61b36a83d76dffac27302e2d1f73b1a3fa6c27b6dd73e492ceb3061f5f9b5a4bd195cdc9dfb91555dfa558dc0aafbf"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parameter_block_layout() -> Result<(), InvalidParameter> {
+    let block = Params::new()
+        .digest_length(32)?
+        .key(b"k")?
+        .max_leaf_length(0x01020304)
+        .node_offset(0x05)
+        .node_depth(6)
+        .inner_hash_length(7)?
+        .salt(b"S")?
+        .personal(b"P")?
+        .to_bytes();
+
+    assert_eq!(&block[..18], &[32, 1, 1, 1, 4, 3, 2, 1, 5, 0, 0, 0, 0, 0, 0, 0, 6, 7]);
+    assert!(block[18..32].iter().all(|&b| b == 0));
+    assert_eq!(block[32], b'S');
+    assert_eq!(block[48], b'P');
+
+    Ok(())
+}
+
+#[test]
+fn default_state_matches_digest() {
+    let mut state = Blake2b::new();
+    state.update(b"ab");
+    state.update(b"c");
+    assert_eq!(state.finalize(), blake2(b"abc", None, 64).unwrap());
+}
+
+// Through the Digest trait, a state with a shorter digest gives just that
+#[test]
+fn digest_trait_with_shorter_lengths() {
+    fn finalize<D: Digest>(state: D) -> D::Output {
+        state.finalize()
+    }
+
+    let mut state = Params::new().digest_length(32).unwrap().to_state();
+    state.update(b"abc");
+    assert_eq!(finalize(state), blake2(b"abc", None, 32).unwrap());
+
+    let mut state = Blake2s::new_keyed(b"key", 16).unwrap();
+    state.update(b"abc");
+    assert_eq!(finalize(state), blake2s(b"abc", Some(b"key"), 16).unwrap());
+
+    assert_eq!(<Blake2b as Digest>::digest(b"abc"), blake2(b"abc", None, 64).unwrap());
+    assert_eq!(<Blake2s as Digest>::digest(b"abc").len(), 32);
+}
+
+#[test]
+fn parameters_out_of_range() {
+    assert_eq!(Params::new().digest_length(0).err(), Some(InvalidParameter::DigestLength(0)));
+    assert_eq!(Params::new().digest_length(65).err(), Some(InvalidParameter::DigestLength(65)));
+    assert_eq!(Params::new().key(&[0; 65]).err(), Some(InvalidParameter::KeyLength(65)));
+    assert_eq!(Params::new().salt(&[0; 17]).err(), Some(InvalidParameter::SaltLength(17)));
+    assert_eq!(Params::new().personal(&[0; 17]).err(), Some(InvalidParameter::PersonalLength(17)));
+    assert_eq!(Params::new().max_depth(0).err(), Some(InvalidParameter::MaxDepth));
+    assert_eq!(Params::new().inner_hash_length(65).err(), Some(InvalidParameter::InnerHashLength(65)));
+
+    // The limits themselves are fine
+    assert!(Params::new().digest_length(64).and_then(|p| p.key(&[0; 64])).is_ok());
+    assert!(Params::new().salt(&[0; 16]).and_then(|p| p.personal(&[0; 16])).is_ok());
+
+    assert_eq!(blake2(b"", None, 0).err(), Some(InvalidParameter::DigestLength(0)));
+    assert_eq!(blake2(b"", Some(&[0; 65]), 64).err(), Some(InvalidParameter::KeyLength(65)));
+    assert_eq!(blake2s(b"", None, 33).err(), Some(InvalidParameter::DigestLength(33)));
+    assert_eq!(Blake2s::new_keyed(&[0; 33], 32).err(), Some(InvalidParameter::KeyLength(33)));
+    assert!(Blake2s::new_keyed(&[0; 32], 1).is_ok());
+}
@@ -0,0 +1,124 @@
+use hash_functions::blake3::{blake3, derive_key, keyed_hash, Blake3};
+
+// From test_vectors.json in the BLAKE3 repository.  The input for each case
+// is the byte sequence 0, 1, ..., 250, 0, 1, ... of the given length.
+const KEY: &[u8; 32] = b"whats the Elvish word for friend";
+const CONTEXT: &str = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+
+const HASH: &[(usize, &str)] = &[
+    (0, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"),
+    (1, "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213"),
+    (1023, "10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11"),
+    (1024, "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7"),
+    (1025, "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444"),
+    (2049, "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030"),
+    (3073, "7124b49501012f81cc7f11ca069ec9226cecb8a2c850cfe644e327d22d3e1cd3"),
+    (8193, "bab6c09cb8ce8cf459261398d2e7aef35700bf488116ceb94a36d0f5f1b7bc3b"),
+    (31744, "62b6960e1a44bcc1eb1a611a8d6235b6b4b78f32e7abc4fb4c6cdcce94895c47"),
+];
+
+const KEYED_HASH: &[(usize, &str)] = &[
+    (0, "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26"),
+    (1, "6d7878dfff2f485635d39013278ae14f1454b8c0a3a2d34bc1ab38228a80c95b"),
+    (1023, "c951ecdf03288d0fcc96ee3413563d8a6d3589547f2c2fb36d9786470f1b9d6e"),
+    (1024, "75c46f6f3d9eb4f55ecaaee480db732e6c2105546f1e675003687c31719c7ba4"),
+    (1025, "357dc55de0c7e382c900fd6e320acc04146be01db6a8ce7210b7189bd664ea69"),
+    (2049, "9f29700902f7c86e514ddc4df1e3049f258b2472b6dd5267f61bf13983b78dd5"),
+    (3073, "68dede9bef00ba89e43f31a6825f4cf433389fedae75c04ee9f0cf16a427c95a"),
+    (8193, "954a2a75420c8d6547e3ba5b98d963e6fa6491addc8c023189cc519821b4a1f5"),
+    (31744, "efa53b389ab67c593dba624d898d0f7353ab99e4ac9d42302ee64cbf9939a419"),
+];
+
+const DERIVE_KEY: &[(usize, &str)] = &[
+    (0, "2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d"),
+    (1, "b3e2e340a117a499c6cf2398a19ee0d29cca2bb7404c73063382693bf66cb06c"),
+    (1023, "74a16c1c3d44368a86e1ca6df64be6a2f64cce8f09220787450722d85725dea5"),
+    (1024, "7356cd7720d5b66b6d0697eb3177d9f8d73a4a5c5e968896eb6a689684302706"),
+    (1025, "effaa245f065fbf82ac186839a249707c3bddf6d3fdda22d1b95a3c970379bcb"),
+    (2049, "2ea477c5515cc3dd606512ee72bb3e0e758cfae7232826f35fb98ca1bcbdf273"),
+    (3073, "72613c9ec9ff7e40f8f5c173784c532ad852e827dba2bf85b2ab4b76f7079081"),
+    (8193, "af1e0346e389b17c23200270a64aa4e1ead98c61695d917de7d5b00491c9b0f1"),
+    (31744, "39772aef80e0ebe60596361e45b061e8f417429d529171b6764468c22928e28e"),
+];
+
+// The full 131 bytes of extended output for the 1025-byte case
+const HASH_XOF_1025: &str = "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444f4c4a22b4b399155358a994e52bf255de60035742ec71bd08ac275a1b51cc6bfe332b0ef84b409108cda080e6269ed4b3e2c3f7d722aa4cdc98d16deb554e5627be8f955c98e1d5f9565a9194cad0c4285f93700062d9595adb992ae68ff12800ab67a";
+
+fn input(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i % 251) as u8).collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn hash() {
+    for &(n, expected) in HASH {
+        assert_eq!(blake3(&input(n)).to_string(), expected, "length {}", n);
+    }
+}
+
+#[test]
+fn keyed() {
+    for &(n, expected) in KEYED_HASH {
+        assert_eq!(keyed_hash(KEY, &input(n)).to_string(), expected, "length {}", n);
+    }
+}
+
+#[test]
+fn derive() {
+    for &(n, expected) in DERIVE_KEY {
+        assert_eq!(hex(&derive_key(CONTEXT, &input(n))), expected, "length {}", n);
+    }
+}
+
+#[test]
+fn extended_output() {
+    let mut hasher = Blake3::new();
+    hasher.update(&input(1025));
+
+    let mut output = [0; 131];
+    hasher.output_xof().read(&mut output);
+    assert_eq!(hex(&output), HASH_XOF_1025);
+
+    // Reading in pieces, or from an offset, gives the same bytes
+    let mut reader = hasher.finalize_xof();
+    let mut pieces = [0; 131];
+    reader.read(&mut pieces[..7]);
+    reader.read(&mut pieces[7..70]);
+    reader.read(&mut pieces[70..]);
+    assert_eq!(pieces, output);
+
+    reader.set_position(100);
+    let mut tail = [0; 31];
+    reader.read(&mut tail);
+    assert_eq!(tail, output[100..]);
+}
+
+#[test]
+fn incremental() {
+    let data = input(31744);
+
+    for step in [1, 63, 64, 1000, 1024, 1025] {
+        let mut hasher = Blake3::new();
+        for piece in data.chunks(step) {
+            hasher.update(piece);
+        }
+        assert_eq!(hasher.finalize(), blake3(&data), "step {}", step);
+    }
+}
+
+// The output so far leaves the hasher to carry on
+#[test]
+fn output_along_the_way() {
+    let data = input(2049);
+    let mut hasher = Blake3::new();
+
+    hasher.update(&data[..1025]);
+    assert_eq!(hasher.output(), blake3(&data[..1025]));
+
+    hasher.update(&data[1025..]);
+    assert_eq!(hasher.output(), blake3(&data));
+    assert_eq!(hasher.finalize(), blake3(&data));
+}
@@ -0,0 +1,185 @@
+use hash_functions::crypt::{crypt, md5_crypt, sha256_crypt, sha512_crypt, verify, UnsupportedScheme};
+
+// The test vectors from Drepper's "Unix crypt using SHA-256 and SHA-512":
+// each setting, password and result
+const SHA256_CASES: &[(&str, &str, &str)] = &[
+    (
+        "$5$saltstring",
+        "Hello world!",
+        "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5",
+    ),
+    (
+        "$5$rounds=10000$saltstringsaltstring",
+        "Hello world!",
+        "$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA",
+    ),
+    (
+        "$5$rounds=5000$toolongsaltstring",
+        "This is just a test",
+        "$5$rounds=5000$toolongsaltstrin$Un/5jzAHMgOGZ5.mWJpuVolil07guHPvOW8mGRcvxa5",
+    ),
+    (
+        "$5$rounds=1400$anotherlongsaltstring",
+        "a very much longer text to encrypt.  This one even stretches over morethan one line.",
+        "$5$rounds=1400$anotherlongsalts$Rx.j8H.h8HjEDGomFU8bDkXm3XIUnzyxf12oP84Bnq1",
+    ),
+    (
+        "$5$rounds=77777$short",
+        "we have a short salt string but not a short password",
+        "$5$rounds=77777$short$JiO1O3ZpDAxGJeaDIuqCoEFysAe1mZNJRs3pw0KQRd/",
+    ),
+    (
+        "$5$rounds=123456$asaltof16chars..",
+        "a short string",
+        "$5$rounds=123456$asaltof16chars..$gP3VQ/6X7UUEW3HkBn2w1/Ptq2jxPyzV/cZKmF/wJvD",
+    ),
+    (
+        "$5$rounds=10$roundstoolow",
+        "the minimum number is still observed",
+        "$5$rounds=1000$roundstoolow$yfvwcWrQ8l/K0DAWyuPMDNHpIVlTQebY9l/gL972bIC",
+    ),
+];
+
+const SHA512_CASES: &[(&str, &str, &str)] = &[
+    (
+        "$6$saltstring",
+        "Hello world!",
+        "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJu\
+         esI68u4OTLiBFdcbYEdFCoEOfaS35inz1",
+    ),
+    (
+        "$6$rounds=10000$saltstringsaltstring",
+        "Hello world!",
+        "$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/UrjmM0Dp8vOu\
+         ZeHBy/YTBmSK6H9qs/y3RnOaw5v.",
+    ),
+    (
+        "$6$rounds=5000$toolongsaltstring",
+        "This is just a test",
+        "$6$rounds=5000$toolongsaltstrin$lQ8jolhgVRVhY4b5pZKaysCLi0QBxGoNeKQzQ3glMhwllF7oGDZxUhx1yxdYcz\
+         /e1JSbq3y6JMxxl8audkUEm0",
+    ),
+    (
+        "$6$rounds=1400$anotherlongsaltstring",
+        "a very much longer text to encrypt.  This one even stretches over morethan one line.",
+        "$6$rounds=1400$anotherlongsalts$POfYwTEok97VWcjxIiSOjiykti.o/pQs.wPvMxQ6Fm7I6IoYN3CmLs66x9t0oS\
+         wbtEW7o7UmJEiDwGqd8p4ur1",
+    ),
+    (
+        "$6$rounds=77777$short",
+        "we have a short salt string but not a short password",
+        "$6$rounds=77777$short$WuQyW2YR.hBNpjjRhpYD/ifIw05xdfeEyQoMxIXbkvr0gge1a1x3yRULJ5CCaUeOxFmtlcGZ\
+         elFl5CxtgfiAc0",
+    ),
+    (
+        "$6$rounds=123456$asaltof16chars..",
+        "a short string",
+        "$6$rounds=123456$asaltof16chars..$BtCwjqMJGx5hrJhZywWvt0RLE8uZ4oPwcelCjmw2kSYu.Ec6ycULevoBK25fs\
+         2xXgMNrCzIMVcgEJAstJeonj1",
+    ),
+    (
+        "$6$rounds=10$roundstoolow",
+        "the minimum number is still observed",
+        "$6$rounds=1000$roundstoolow$kUMsbe306n21p9R.FRkW3IGn.S9NPN0x50YhH1xhLsPuWGsUSklZt58jaTfF4ZEQpy\
+         UNGc0dqbpBYYBaHHrsX.",
+    ),
+];
+
+// From glibc's crypt(3)
+const MD5_CASES: &[(&str, &str, &str)] = &[
+    ("$1$saltstring", "Hello world!", "$1$saltstri$YMyguxXMBpd2TEZ.vS/3q1"),
+    ("$1$abc", "", "$1$abc$Or2rbeUYTvt12aiVzMuS/."),
+    ("$1$12345678", "password", "$1$12345678$o2n/JiO/h5VviOInWJ4OQ/"),
+    (
+        "$1$xyzzy",
+        "a password longer than sixteen bytes, and then some",
+        "$1$xyzzy$j2F27nQW5Vm.Yx3mCz0V.1",
+    ),
+    ("$1$", "empty salt", "$1$$ObZkFeusGD7Pq0bDVWvPA/"),
+];
+
+fn check(cases: &[(&str, &str, &str)]) {
+    for &(setting, password, expected) in cases {
+        assert_eq!(crypt(password.as_bytes(), setting).unwrap(), expected);
+
+        // The whole string works as a setting too, which is how checking
+        // a password works
+        assert_eq!(verify(password.as_bytes(), expected), Ok(true));
+    }
+}
+
+#[test]
+fn sha256_drepper() {
+    check(SHA256_CASES);
+}
+
+#[test]
+fn sha512_drepper() {
+    check(SHA512_CASES);
+}
+
+#[test]
+fn md5() {
+    check(MD5_CASES);
+}
+
+#[test]
+fn direct_functions() {
+    assert_eq!(md5_crypt(b"password", "12345678"), "$1$12345678$o2n/JiO/h5VviOInWJ4OQ/");
+    assert_eq!(
+        sha256_crypt(b"Hello world!", "saltstring", None),
+        "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5"
+    );
+
+    // Asking for 5000 rounds gives the same hash as the default, but says so
+    let default = sha512_crypt(b"x", "salt", None);
+    let explicit = sha512_crypt(b"x", "salt", Some(5000));
+    assert_eq!(explicit, default.replace("$6$", "$6$rounds=5000$"));
+    assert_eq!(
+        default,
+        "$6$salt$wZU8LXJfJJqoagopbB7RuK6JEotEMZ0CQDy0phpPAuLMYQFcmf6L6BdAbs/Q7w7o1qsZ9pFqFVY4yuUSWgaYt1"
+    );
+}
+
+// glibc reads the count with strtoul, and takes it whenever a '$' follows
+#[test]
+fn rounds_as_glibc_reads_them() {
+    let at_least = crypt(b"password", "$5$rounds=1000$saltstring").unwrap();
+    assert!(at_least.starts_with("$5$rounds=1000$saltstring$"));
+
+    // No digits at all is 0, brought up to 1000
+    assert_eq!(crypt(b"password", "$5$rounds=$saltstring").unwrap(), at_least);
+    assert_eq!(crypt(b"password", "$5$rounds=0$saltstring").unwrap(), at_least);
+    assert_eq!(crypt(b"password", "$5$rounds=-0$saltstring").unwrap(), at_least);
+    assert_eq!(crypt(b"password", "$5$rounds= +12$saltstring").unwrap(), at_least);
+
+    // Anything else is just part of the salt
+    for setting in ["$5$rounds=12x$salt", "$5$rounds=+$salt", "$5$rounds=1000"] {
+        let salt = &setting[3..];
+        assert_eq!(
+            crypt(b"password", setting).unwrap(),
+            sha256_crypt(b"password", salt, None),
+            "{}",
+            setting
+        );
+    }
+}
+
+#[test]
+fn wrong_passwords() {
+    let shadow = "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1";
+    assert_eq!(verify(b"Hello world!", shadow), Ok(true));
+    assert_eq!(verify(b"Hello world", shadow), Ok(false));
+    assert_eq!(verify(b"", shadow), Ok(false));
+
+    // A different salt or round count is a different hash
+    assert_eq!(verify(b"Hello world!", &shadow.replace("saltstring", "saltstrinG")), Ok(false));
+    assert_eq!(verify(b"Hello world!", &shadow.replace("$6$", "$6$rounds=5001$")), Ok(false));
+}
+
+#[test]
+fn unsupported() {
+    for shadow in ["", "!", "*", "!$6$salt$hash", "$2b$10$abcdefghijklmnopqrstuv", "$y$j9T$salt$hash", "$6"] {
+        assert_eq!(verify(b"password", shadow), Err(UnsupportedScheme), "{:?}", shadow);
+    }
+}
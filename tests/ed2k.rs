@@ -0,0 +1,105 @@
+use hash_functions::ed2k::{ed2k, link, Aich, Convention, Ed2k, AICH_BLOCK_SIZE, PART_SIZE};
+use hash_functions::Digest;
+
+const PART: usize = PART_SIZE as usize;
+const BLOCK: usize = AICH_BLOCK_SIZE as usize;
+
+fn input(length: usize) -> Vec<u8> {
+    (0..length).map(|i| ((i * 7 + i / 1000) % 251) as u8).collect()
+}
+
+#[test]
+fn small_files_are_md4() {
+    assert_eq!(ed2k(b"").to_string(), "31d6cfe0d16ae931b73c59d7e0c089c0");
+    assert_eq!(ed2k(b"abc").to_string(), "a448017aaf21d8525fc10ae87aa6729d");
+
+    // Nothing to tell the conventions apart
+    let mut hasher = Ed2k::new();
+    hasher.update(b"abc");
+    assert_eq!(hasher.finalize_with(Convention::Old).to_string(), "a448017aaf21d8525fc10ae87aa6729d");
+}
+
+// The example of the two conventions that does the rounds: one part of
+// zeros
+#[test]
+fn one_part_of_zeros() {
+    let mut hasher = Ed2k::new();
+    hasher.update(&vec![0; PART]);
+
+    assert_eq!(hasher.clone().finalize_with(Convention::New).to_string(), "d7def262a127cd79096a108e7a9fc138");
+    assert_eq!(hasher.finalize_with(Convention::Old).to_string(), "fc21d9af828f92a8df64beac3357425d");
+}
+
+// Against a Python model of eMule's code, with OpenSSL's MD4: the ed2k
+// hash by the new and old conventions, then the AICH root
+#[test]
+fn part_boundaries() {
+    let cases = [
+        (0, "31d6cfe0d16ae931b73c59d7e0c089c0", "31d6cfe0d16ae931b73c59d7e0c089c0", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+        (1, "47c61a0fa8738ba77308a8a600f88e4b", "47c61a0fa8738ba77308a8a600f88e4b", "5ba93c9db0cff93f52b521d7420e43f6eda2784f"),
+        (BLOCK, "f41bf755909ab70193fb5350cae392c5", "f41bf755909ab70193fb5350cae392c5", "2265af71dba573102d5c6e8321ea72fae3d09f38"),
+        (BLOCK + 1, "d05fd484f40d73aa59137070d57a9aed", "d05fd484f40d73aa59137070d57a9aed", "79f9fcc8815ee6426e10f9e91c45ae534357c231"),
+        (PART - 1, "615136717b87222cfc082dc12b16a083", "615136717b87222cfc082dc12b16a083", "6619469a9a8ce1a858d9d722908829de0238885c"),
+        (PART, "c493d2fe435506a86a1113dec99d8a9f", "9b065d5166ce7d552227b8cd3d8da06a", "17a999ad59da2ec4f64f896b8a7a6911aa71ebbf"),
+        (PART + 1, "2e289e440c0fe13d3eea62c87f4185e4", "2e289e440c0fe13d3eea62c87f4185e4", "b4e575060d19f646de3d3c99ef1e8079545f44d9"),
+        (2 * PART, "5f6f96bfef514d48f3aba50b2a562435", "e6009f30299d7abd8d820060264a5905", "69cf452d0b4d2efc6e8049efee781f23f67c950a"),
+        (2 * PART + 500_000, "f307f03f02eda546c95c02573e8e8eca", "f307f03f02eda546c95c02573e8e8eca", "ff14c9507c407af4f13a9f5e6e9be1d4f0a31879"),
+        (3 * PART, "4d7d7142075d59191991dc8ef42b5c5e", "9df42d7eb591a672841092ddb09ee07f", "6f8237005cf216341afb3efa415d26f6ef7b5854"),
+    ];
+
+    let input = input(3 * PART);
+
+    for (length, new, old, aich) in cases {
+        let mut hasher = Ed2k::new();
+        hasher.update(&input[..length]);
+        assert_eq!(hasher.clone().finalize().to_string(), new, "{} bytes", length);
+        assert_eq!(hasher.finalize_with(Convention::Old).to_string(), old, "{} bytes", length);
+
+        assert_eq!(Aich::digest(&input[..length]).to_string(), aich, "{} bytes", length);
+    }
+}
+
+// Pieces that straddle the part and block boundaries
+#[test]
+fn incremental() {
+    let input = input(2 * PART + 500_000);
+
+    let mut ed2k = Ed2k::new();
+    let mut aich = Aich::new();
+    for chunk in input.chunks(BLOCK + 12_345) {
+        ed2k.update(chunk);
+        aich.update(chunk);
+    }
+
+    assert_eq!(ed2k.finalize().to_string(), "f307f03f02eda546c95c02573e8e8eca");
+    assert_eq!(aich.finalize().to_string(), "ff14c9507c407af4f13a9f5e6e9be1d4f0a31879");
+}
+
+// Through Digest, blocks are the parts and leaves that get hashed whole
+#[test]
+fn block_sizes() {
+    assert_eq!(Ed2k::BLOCK_SIZE, PART);
+    assert_eq!(Aich::BLOCK_SIZE, BLOCK);
+}
+
+#[test]
+fn links() {
+    let input = input(PART + 1);
+    let ed2k = ed2k(&input);
+    let aich = Aich::digest(&input);
+
+    assert_eq!(
+        link("part.bin", input.len() as u64, &ed2k, Some(&aich)),
+        "ed2k://|file|part.bin|9728001|2e289e440c0fe13d3eea62c87f4185e4|h=WTSXKBQNDH3ENXR5HSM66HUAPFKF6RGZ|/"
+    );
+    assert_eq!(
+        link("part.bin", input.len() as u64, &ed2k, None),
+        "ed2k://|file|part.bin|9728001|2e289e440c0fe13d3eea62c87f4185e4|/"
+    );
+
+    // The separator, spaces and anything outside ASCII are escaped
+    assert_eq!(
+        link("My Song|Mix (ü).mp3", 0, &Ed2k::digest(b""), None),
+        "ed2k://|file|My%20Song%7CMix%20%28%C3%BC%29.mp3|0|31d6cfe0d16ae931b73c59d7e0c089c0|/"
+    );
+}
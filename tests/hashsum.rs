@@ -0,0 +1,468 @@
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+const HASHSUM: &str = env!("CARGO_BIN_EXE_hashsum");
+
+const MD5_ABC: &str = "900150983cd24fb0d6963f7d28e17f72";
+const MD5_EMPTY: &str = "d41d8cd98f00b204e9800998ecf8427e";
+const SHA1_ABC: &str = "a9993e364706816aba3e25717850c26c9cd0d89d";
+const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+// A directory of the test's own holding abc.txt and empty.txt, for hashsum
+// to run in so that the names it prints are short
+fn scratch(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("hashsum-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(dir.join("abc.txt"), b"abc").unwrap();
+    fs::write(dir.join("empty.txt"), b"").unwrap();
+    dir
+}
+
+fn hashsum(dir: &Path) -> Command {
+    let mut command = Command::new(HASHSUM);
+    command.current_dir(dir);
+    command
+}
+
+// Run with `input` on standard input
+fn run(command: &mut Command, input: &[u8]) -> Output {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+// With the program as just "hashsum", wherever it was run from
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap().replace(HASHSUM, "hashsum")
+}
+
+#[test]
+fn algorithm_option() {
+    let dir = scratch("algorithm");
+
+    for args in [&["-a", "md5"][..], &["--algorithm", "md5"], &["--algorithm=MD5"], &["-amd5"]] {
+        let output = run(hashsum(&dir).args(args).arg("abc.txt"), b"");
+        assert!(output.status.success(), "{:?}", args);
+        assert_eq!(stdout(&output), format!("{}  abc.txt\n", MD5_ABC), "{:?}", args);
+    }
+
+    // SHA-256 by default
+    let output = run(hashsum(&dir).arg("abc.txt"), b"");
+    assert_eq!(stdout(&output), format!("{}  abc.txt\n", SHA256_ABC));
+
+    let output = run(hashsum(&dir).args(["-a", "md6", "abc.txt"]), b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("unknown algorithm 'md6'"));
+    assert!(output.stdout.is_empty());
+
+    let output = run(hashsum(&dir).arg("-a"), b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("option requires an argument -- 'a'"));
+}
+
+#[test]
+fn multiple_files() {
+    let dir = scratch("multiple");
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "abc.txt", "empty.txt", "abc.txt"]), b"");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        format!("{0}  abc.txt\n{1}  empty.txt\n{0}  abc.txt\n", MD5_ABC, MD5_EMPTY)
+    );
+}
+
+#[test]
+fn standard_input() {
+    let dir = scratch("stdin");
+
+    let output = run(hashsum(&dir).args(["-a", "sha1", "-"]), b"abc");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{}  -\n", SHA1_ABC));
+
+    // With no files at all, and among them
+    let output = run(hashsum(&dir).args(["-a", "sha1"]), b"abc");
+    assert_eq!(stdout(&output), format!("{}  -\n", SHA1_ABC));
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "empty.txt", "-"]), b"abc");
+    assert_eq!(stdout(&output), format!("{}  empty.txt\n{}  -\n", MD5_EMPTY, MD5_ABC));
+}
+
+// The files that can be read still are, and the exit status says that one
+// couldn't be
+#[test]
+fn missing_file() {
+    let dir = scratch("missing");
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "abc.txt", "missing.txt", "empty.txt"]), b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), format!("{}  abc.txt\n{}  empty.txt\n", MD5_ABC, MD5_EMPTY));
+    assert_eq!(stderr(&output), "hashsum: missing.txt: No such file or directory\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn named_after_coreutils_tools() {
+    let dir = scratch("symlinks");
+
+    for (tool, expected) in [("md5sum", MD5_ABC), ("sha1sum", SHA1_ABC), ("sha256sum", SHA256_ABC)] {
+        let link = dir.join(tool);
+        std::os::unix::fs::symlink(HASHSUM, &link).unwrap();
+
+        let mut command = Command::new(&link);
+        command.current_dir(&dir).arg("abc.txt");
+        let output = run(&mut command, b"");
+        assert!(output.status.success(), "{}", tool);
+        assert_eq!(stdout(&output), format!("{}  abc.txt\n", expected), "{}", tool);
+    }
+
+    // -a still wins
+    let mut command = Command::new(dir.join("md5sum"));
+    command.current_dir(&dir).args(["-a", "sha1", "abc.txt"]);
+    assert_eq!(stdout(&run(&mut command, b"")), format!("{}  abc.txt\n", SHA1_ABC));
+}
+
+// Linux file names can be any bytes, and they're printed as they are
+#[cfg(target_os = "linux")]
+#[test]
+fn file_names_that_are_not_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = scratch("not-utf8");
+    let name = OsStr::from_bytes(b"caf\xe9.txt");
+    fs::write(dir.join(name), b"abc").unwrap();
+
+    let output = run(hashsum(&dir).args(["-a".as_ref(), "md5".as_ref(), name]), b"");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(output.stdout, [format!("{}  ", MD5_ABC).as_bytes(), b"caf\xe9.txt\n"].concat());
+}
+
+#[test]
+fn binary_marker() {
+    let dir = scratch("binary");
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "-b", "abc.txt"]), b"");
+    assert_eq!(stdout(&output), format!("{} *abc.txt\n", MD5_ABC));
+
+    // Text mode is the default, and the last of the two wins
+    let output = run(hashsum(&dir).args(["-a", "md5", "-t", "abc.txt"]), b"");
+    assert_eq!(stdout(&output), format!("{}  abc.txt\n", MD5_ABC));
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "-b", "-t", "abc.txt", "-"]), b"");
+    assert_eq!(stdout(&output), format!("{0}  abc.txt\n{1}  -\n", MD5_ABC, MD5_EMPTY));
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "--text", "--binary", "-"]), b"");
+    assert_eq!(stdout(&output), format!("{} *-\n", MD5_EMPTY));
+}
+
+// A name with a line break or backslash in it is escaped, and its line
+// marked with a leading backslash, so that it stays on one line
+#[test]
+fn escaped_names() {
+    let dir = scratch("escaped");
+    fs::write(dir.join("two\nlines"), b"abc").unwrap();
+    fs::write(dir.join("back\\slash"), b"abc").unwrap();
+    fs::write(dir.join("carriage\rreturn"), b"abc").unwrap();
+
+    let output = run(
+        hashsum(&dir).args(["-a", "md5", "abc.txt", "two\nlines", "back\\slash", "carriage\rreturn"]),
+        b"",
+    );
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{0}  abc.txt\n\\{0}  two\\nlines\n\\{0}  back\\\\slash\n\\{0}  carriage\\rreturn\n",
+            MD5_ABC
+        )
+    );
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "-b", "two\nlines"]), b"");
+    assert_eq!(stdout(&output), format!("\\{} *two\\nlines\n", MD5_ABC));
+
+    // And they check back
+    fs::write(dir.join("SUMS"), &output.stdout).unwrap();
+    let output = run(hashsum(&dir).args(["-a", "md5", "-c", "SUMS"]), b"");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn bsd_tags() {
+    let dir = scratch("tag");
+    fs::write(dir.join("two\nlines"), b"abc").unwrap();
+
+    let output = run(hashsum(&dir).args(["--tag", "abc.txt"]), b"");
+    assert_eq!(stdout(&output), format!("SHA256 (abc.txt) = {}\n", SHA256_ABC));
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "--tag", "abc.txt", "two\nlines", "-"]), b"");
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "MD5 (abc.txt) = {0}\n\\MD5 (two\\nlines) = {0}\nMD5 (-) = {1}\n",
+            MD5_ABC, MD5_EMPTY
+        )
+    );
+
+    // Binary mode is implied, and text mode refused
+    let output = run(hashsum(&dir).args(["-a", "sha1", "--tag", "-b", "abc.txt"]), b"");
+    assert_eq!(stdout(&output), format!("SHA1 (abc.txt) = {}\n", SHA1_ABC));
+
+    let output = run(hashsum(&dir).args(["--tag", "-t", "abc.txt"]), b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("hashsum: --tag does not support --text mode\n"));
+}
+
+// Lines end in NUL, and names aren't escaped since a newline in one can't
+// be confused with the end of the line
+#[test]
+fn zero_terminated() {
+    let dir = scratch("zero");
+    fs::write(dir.join("two\nlines"), b"abc").unwrap();
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "-z", "abc.txt", "two\nlines"]), b"");
+    assert_eq!(stdout(&output), format!("{0}  abc.txt\0{0}  two\nlines\0", MD5_ABC));
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "-bz", "abc.txt"]), b"");
+    assert_eq!(stdout(&output), format!("{} *abc.txt\0", MD5_ABC));
+
+    let output = run(hashsum(&dir).args(["-a", "md5", "--tag", "--zero", "two\nlines"]), b"");
+    assert_eq!(stdout(&output), format!("MD5 (two\nlines) = {}\0", MD5_ABC));
+}
+
+// Checking: OK for each file that matches, and then a count of everything
+// that didn't, as coreutils does
+fn check(dir: &Path, list: &str, args: &[&str]) -> Output {
+    fs::write(dir.join("SUMS"), list).unwrap();
+    run(hashsum(dir).args(["-a", "md5", "-c"]).args(args).arg("SUMS"), b"")
+}
+
+#[test]
+fn check_gnu_lines() {
+    let dir = scratch("check-gnu");
+
+    // A space, or a * for binary mode, before the name
+    let list = format!("{}  abc.txt\n{} *empty.txt\n", MD5_ABC, MD5_EMPTY);
+    let output = check(&dir, &list, &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "abc.txt: OK\nempty.txt: OK\n");
+    assert_eq!(stderr(&output), "");
+
+    // Carriage returns, comments, blank lines and no final newline are all
+    // fine
+    let list = format!("# made by hand\r\n\n{}  abc.txt\r\n{}  empty.txt", MD5_ABC, MD5_EMPTY);
+    let output = check(&dir, &list, &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "abc.txt: OK\nempty.txt: OK\n");
+
+    let list = format!("{}  abc.txt\n", MD5_EMPTY);
+    let output = check(&dir, &list, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "abc.txt: FAILED\n");
+    assert_eq!(stderr(&output), "hashsum: WARNING: 1 computed checksum did NOT match\n");
+}
+
+// BSD lines name their algorithm, whatever -a says
+#[test]
+fn check_bsd_lines() {
+    let dir = scratch("check-bsd");
+
+    let list = format!(
+        "MD5 (abc.txt) = {}\nSHA1 (abc.txt) = {}\nSHA256 (abc.txt) = {}\n{}  empty.txt\n",
+        MD5_ABC, SHA1_ABC, SHA256_ABC, MD5_EMPTY
+    );
+    let output = check(&dir, &list, &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "abc.txt: OK\nabc.txt: OK\nabc.txt: OK\nempty.txt: OK\n");
+
+    // Digests of the wrong length for the tag don't count
+    let list = format!("SHA1 (abc.txt) = {}\n", MD5_ABC);
+    let output = check(&dir, &list, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "hashsum: SUMS: no properly formatted checksum lines found\n");
+}
+
+// A line starting with a backslash has its name unescaped
+#[test]
+fn check_escaped_names() {
+    let dir = scratch("check-escaped");
+    fs::write(dir.join("two\nlines"), b"abc").unwrap();
+    fs::write(dir.join("back\\slash"), b"").unwrap();
+
+    let list = format!("\\{}  two\\nlines\n\\{}  back\\\\slash\n", MD5_ABC, MD5_EMPTY);
+    let output = check(&dir, &list, &[]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    // Only the line break needs escaping again to report on
+    assert_eq!(stdout(&output), "\\two\\nlines: OK\nback\\slash: OK\n");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn check_names_that_are_not_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = scratch("check-not-utf8");
+    fs::write(dir.join(OsStr::from_bytes(b"caf\xe9.txt")), b"abc").unwrap();
+
+    let list = [format!("{}  ", MD5_ABC).as_bytes(), b"caf\xe9.txt\n"].concat();
+    fs::write(dir.join("SUMS"), list).unwrap();
+    let output = run(hashsum(&dir).args(["-a", "md5", "-c", "SUMS"]), b"");
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert_eq!(output.stdout, b"caf\xe9.txt: OK\n");
+}
+
+// One of everything: a match, a mismatch, a missing file and a line that
+// isn't a checksum at all
+fn mixed_list() -> String {
+    format!(
+        "{0}  abc.txt\nnot a checksum line\n{1}  abc.txt\n{0}  missing.txt\n",
+        MD5_ABC, MD5_EMPTY
+    )
+}
+
+#[test]
+fn check_reports() {
+    let dir = scratch("check-reports");
+
+    let output = check(&dir, &mixed_list(), &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "abc.txt: OK\nabc.txt: FAILED\nmissing.txt: FAILED open or read\n");
+    assert_eq!(
+        stderr(&output),
+        "hashsum: missing.txt: No such file or directory
+hashsum: WARNING: 1 line is improperly formatted
+hashsum: WARNING: 1 listed file could not be read
+hashsum: WARNING: 1 computed checksum did NOT match
+"
+    );
+
+    // --warn says where
+    let output = check(&dir, &mixed_list(), &["-w"]);
+    assert!(stderr(&output).starts_with("hashsum: SUMS: 2: improperly formatted MD5 checksum line\n"));
+}
+
+#[test]
+fn check_quiet() {
+    let dir = scratch("check-quiet");
+
+    let output = check(&dir, &mixed_list(), &["--quiet"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "abc.txt: FAILED\nmissing.txt: FAILED open or read\n");
+
+    let output = check(&dir, &format!("{}  abc.txt\n", MD5_ABC), &["--quiet"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn check_status() {
+    let dir = scratch("check-status");
+
+    // Only errors opening files get through
+    let output = check(&dir, &mixed_list(), &["--status"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(stderr(&output), "hashsum: missing.txt: No such file or directory\n");
+
+    let output = check(&dir, &format!("{}  abc.txt\n", MD5_EMPTY), &["--status"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output) + &stderr(&output), "");
+
+    let output = check(&dir, &format!("{}  abc.txt\n", MD5_ABC), &["--status"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output) + &stderr(&output), "");
+}
+
+#[test]
+fn check_strict() {
+    let dir = scratch("check-strict");
+    let list = format!("{}  abc.txt\nnot a checksum line\n", MD5_ABC);
+
+    // A bad line is only a warning without --strict
+    let output = check(&dir, &list, &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stderr(&output), "hashsum: WARNING: 1 line is improperly formatted\n");
+
+    let output = check(&dir, &list, &["--strict"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "abc.txt: OK\n");
+}
+
+#[test]
+fn check_ignore_missing() {
+    let dir = scratch("check-ignore-missing");
+    let list = format!("{}  abc.txt\n{}  missing.txt\n", MD5_ABC, MD5_ABC);
+
+    let output = check(&dir, &list, &["--ignore-missing"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "abc.txt: OK\n");
+    assert_eq!(stderr(&output), "");
+
+    // But something has to be there
+    let output = check(&dir, &format!("{}  missing.txt\n", MD5_ABC), &["--ignore-missing"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "hashsum: SUMS: no file was verified\n");
+}
+
+#[test]
+fn single_algorithm_binaries_need_a_file() {
+    let output = run(&mut Command::new(env!("CARGO_BIN_EXE_md5")), b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "Usage: md5 FILE\n");
+
+    let output = run(&mut Command::new(env!("CARGO_BIN_EXE_shake128")), b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "Usage: shake128 FILE [LENGTH]\n");
+}
+
+#[test]
+fn speed_table() {
+    let dir = scratch("speed");
+
+    let output = run(hashsum(&dir).args(["speed", "-s", "0.001", "--cpu-mhz", "1000", "md5", "sha1"]), b"");
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let out = stdout(&output);
+    let lines: Vec<&str> = out.lines().collect();
+    let header = format!(
+        "{:<12}{:>10}{:>10}{:>10}{:>10}{:>10}{:>10}{:>10}",
+        "algorithm", "16 B", "64 B", "256 B", "1 KiB", "8 KiB", "16 KiB", "1 MiB"
+    );
+
+    assert_eq!(lines.len(), 9, "{}", out);
+    assert_eq!(lines[0], "Throughput in MB/s, 0.001 s per size");
+    assert_eq!(lines[1], header);
+    assert!(lines[2].starts_with("md5 ") && lines[3].starts_with("sha1 "), "{}", out);
+    assert_eq!(lines[4], "");
+    assert_eq!(lines[5], "Cycles per byte, estimated at 1000 MHz");
+    assert_eq!(lines[6], header);
+
+    // A cell for every size, rates to one decimal place and cycles to two
+    for (line, places) in [(lines[2], 1), (lines[3], 1), (lines[7], 2), (lines[8], 2)] {
+        let cells: Vec<&str> = line[12..].split_whitespace().collect();
+        assert_eq!(cells.len(), 7, "{}", line);
+        for cell in cells {
+            let (_, fraction) = cell.split_once('.').unwrap();
+            assert_eq!(fraction.len(), places, "{}", line);
+        }
+    }
+
+    let output = run(hashsum(&dir).args(["speed", "-s", "0"]), b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("invalid number of seconds '0'"));
+}
@@ -0,0 +1,166 @@
+use hash_functions::hkdf::{extract, hkdf, Hkdf, InvalidLength};
+use hash_functions::sha1::Sha1;
+use hash_functions::sha256::Sha256;
+use hash_functions::sha512::Sha512;
+use hash_functions::Digest;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn range(from: usize, to: usize) -> Vec<u8> {
+    (from..to).map(|b| b as u8).collect()
+}
+
+struct Case {
+    salt: Vec<u8>,
+    ikm: Vec<u8>,
+    info: Vec<u8>,
+    prk: &'static str,
+    okm: &'static str,
+}
+
+fn check<D: Digest + Clone>(case: &Case) {
+    assert_eq!(extract::<D>(&case.salt, &case.ikm).to_string(), case.prk);
+
+    let mut okm = vec![0; case.okm.len() / 2];
+    hkdf::<D>(&case.salt, &case.ikm, &case.info, &mut okm).unwrap();
+    assert_eq!(hex(&okm), case.okm);
+}
+
+// RFC 5869 appendix A.  Cases 1-3 are for SHA-256, 4-7 for SHA-1.
+
+#[test]
+fn rfc5869_sha256() {
+    check::<Sha256>(&Case {
+        salt: range(0x00, 0x0d),
+        ikm: vec![0x0b; 22],
+        info: range(0xf0, 0xfa),
+        prk: "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+        okm: "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+    });
+
+    check::<Sha256>(&Case {
+        salt: range(0x60, 0xb0),
+        ikm: range(0x00, 0x50),
+        info: range(0xb0, 0x100),
+        prk: "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+        okm: "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c\
+              59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71\
+              cc30c58179ec3e87c14c01d5c1f3434f1d87",
+    });
+
+    check::<Sha256>(&Case {
+        salt: vec![],
+        ikm: vec![0x0b; 22],
+        info: vec![],
+        prk: "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+        okm: "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8",
+    });
+}
+
+#[test]
+fn rfc5869_sha1() {
+    check::<Sha1>(&Case {
+        salt: range(0x00, 0x0d),
+        ikm: vec![0x0b; 11],
+        info: range(0xf0, 0xfa),
+        prk: "9b6c18c432a7bf8f0e71c8eb88f4b30baa2ba243",
+        okm: "085a01ea1b10f36933068b56efa5ad81a4f14b822f5b091568a9cdd4f155fda2c22e422478d305f3f896",
+    });
+
+    check::<Sha1>(&Case {
+        salt: range(0x60, 0xb0),
+        ikm: range(0x00, 0x50),
+        info: range(0xb0, 0x100),
+        prk: "8adae09a2a307059478d309b26c4115a224cfaf6",
+        okm: "0bd770a74d1160f7c9f12cd5912a06ebff6adcae899d92191fe4305673ba2ffe\
+              8fa3f1a4e5ad79f3f334b3b202b2173c486ea37ce3d397ed034c7f9dfeb15c5e\
+              927336d0441f4c4300e2cff0d0900b52d3b4",
+    });
+
+    check::<Sha1>(&Case {
+        salt: vec![],
+        ikm: vec![0x0b; 22],
+        info: vec![],
+        prk: "da8c8a73c7fa77288ec6f5e7c297786aa0d32d01",
+        okm: "0ac1af7002b3d761d1e55298da9d0506b9ae52057220a306e07b6b87e8df21d0ea00033de03984d34918",
+    });
+
+    // Case 7 leaves the salt out entirely, which means a hash length of
+    // zeros
+    check::<Sha1>(&Case {
+        salt: vec![0; 20],
+        ikm: vec![0x0c; 22],
+        info: vec![],
+        prk: "2adccada18779e7c2077ad2eb19d3f3e731385dd",
+        okm: "2c91117204d745f3500d636a62f64f0ab3bae548aa53d423b0d1f27ebba6f5e5673a081d70cce7acfc48",
+    });
+    assert_eq!(
+        extract::<Sha1>(&[], &[0x0c; 22]).to_string(),
+        "2adccada18779e7c2077ad2eb19d3f3e731385dd"
+    );
+}
+
+// The RFC has no SHA-512 cases; these come from a direct transcription of
+// its definitions on top of Python's hmac module.
+
+#[test]
+fn sha512() {
+    check::<Sha512>(&Case {
+        salt: range(0x00, 0x0d),
+        ikm: vec![0x0b; 22],
+        info: range(0xf0, 0xfa),
+        prk: "665799823737ded04a88e47e54a5890bb2c3d247c7a4254a8e61350723590a26\
+              c36238127d8661b88cf80ef802d57e2f7cebcf1e00e083848be19929c61b4237",
+        okm: "832390086cda71fb47625bb5ceb168e4c8e26a1a16ed34d9fc7fe92c1481579338da362cb8d9f925d7cb",
+    });
+
+    // Several blocks, ending part way through one
+    check::<Sha512>(&Case {
+        salt: vec![],
+        ikm: vec![0x0b; 22],
+        info: vec![],
+        prk: "fd200c4987ac491313bd4a2a13287121247239e11c9ef82802044b66ef357e5b\
+              194498d0682611382348572a7b1611de54764094286320578a863f36562b0df6",
+        okm: "f5fa02b18298a72a8c23898a8703472c6eb179dc204c03425c970e3b164bf90f\
+              ff22d04836d0e2343bacc4e7cb6045faaa698e0e3b3eb91331306def1db8319e\
+              8a699b5ee45ab993847dc4df75bde023692c8c0710a67a55123f10a8b2d8327f\
+              9eb138da69d5bea1e09a39ea99a341c00b2c9ee0d4ba632115aec516bb71e922\
+              7cb00ca98b7dfa8f1c7eca60c931281fa85b872f70b4af4bc143f865477a6e87\
+              3f3c91050370cb6adf5b9289c9f1d18bb572ae4773b3ff7eca9c0220f13974c1\
+              38db3874568a4c1f",
+    });
+}
+
+#[test]
+fn output_length_limit() {
+    let prk = Hkdf::<Sha256>::new(b"salt", b"ikm");
+
+    let mut okm = vec![0; 255 * 32];
+    assert_eq!(prk.expand(b"", &mut okm), Ok(()));
+
+    let mut okm = vec![0; 255 * 32 + 1];
+    assert_eq!(
+        prk.expand(b"", &mut okm),
+        Err(InvalidLength {
+            requested: 255 * 32 + 1,
+            max: 255 * 32,
+        })
+    );
+    assert!(okm.iter().all(|&b| b == 0));
+
+    let mut okm = vec![0; 255 * 64 + 1];
+    assert!(hkdf::<Sha512>(b"", b"ikm", b"", &mut okm).is_err());
+}
+
+#[test]
+fn expand_is_a_prefix() {
+    let prk = Hkdf::<Sha256>::from_prk(&[0x42; 32]);
+
+    let mut long = [0; 100];
+    let mut short = [0; 33];
+    prk.expand(b"info", &mut long).unwrap();
+    prk.expand(b"info", &mut short).unwrap();
+    assert_eq!(&long[..33], &short[..]);
+}
@@ -0,0 +1,173 @@
+use hash_functions::hmac::{hmac, Hmac};
+use hash_functions::md5::Md5;
+use hash_functions::ripemd160::Ripemd160;
+use hash_functions::sha1::Sha1;
+use hash_functions::sha256::{Sha224, Sha256};
+use hash_functions::sha512::{Sha384, Sha512};
+use hash_functions::Digest;
+
+// RFC 4231 section 4: the same seven keys and messages for every SHA-2
+// variant.  Case 5's tags are truncated to 128 bits.
+fn rfc4231_cases() -> Vec<(Vec<u8>, Vec<u8>)> {
+    vec![
+        (vec![0x0b; 20], b"Hi There".to_vec()),
+        (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+        (vec![0xaa; 20], vec![0xdd; 50]),
+        ((1..=25).collect(), vec![0xcd; 50]),
+        (vec![0x0c; 20], b"Test With Truncation".to_vec()),
+        (vec![0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec()),
+        (
+            vec![0xaa; 131],
+            b"This is a test using a larger than block-size key and a larger than block-size \
+              data. The key needs to be hashed before being used by the HMAC algorithm."
+                .to_vec(),
+        ),
+    ]
+}
+
+// RFC 2202 (MD5, SHA-1) and RFC 2286 (RIPEMD-160) share another seven,
+// with keys sized to the digest.  Case 5 is given in full here.
+fn rfc2202_cases(key_length: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+    vec![
+        (vec![0x0b; key_length], b"Hi There".to_vec()),
+        (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+        (vec![0xaa; key_length], vec![0xdd; 50]),
+        ((1..=25).collect(), vec![0xcd; 50]),
+        (vec![0x0c; key_length], b"Test With Truncation".to_vec()),
+        (vec![0xaa; 80], b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec()),
+        (
+            vec![0xaa; 80],
+            b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data".to_vec(),
+        ),
+    ]
+}
+
+fn check<D: Digest + Clone>(cases: &[(Vec<u8>, Vec<u8>)], expected: &[&str]) {
+    for (i, ((key, message), expected)) in cases.iter().zip(expected).enumerate() {
+        let tag = hmac::<D>(key, message).to_string();
+        assert!(tag.starts_with(expected), "{} case {}: {}", D::NAME, i + 1, tag);
+
+        // And again with the message split up, from a cloned key state
+        let keyed = Hmac::<D>::new(key);
+        let mut mac = keyed.clone();
+        for piece in message.chunks(7) {
+            mac.update(piece);
+        }
+        assert_eq!(mac.finalize().to_string(), tag, "{} case {}", D::NAME, i + 1);
+    }
+}
+
+#[test]
+fn rfc4231_sha224() {
+    check::<Sha224>(&rfc4231_cases(), &[
+        "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+        "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+        "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+        "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+        "0e2aea68a90c8d37c988bcdb9fca6fa8",
+        "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+        "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+    ]);
+}
+
+#[test]
+fn rfc4231_sha256() {
+    check::<Sha256>(&rfc4231_cases(), &[
+        "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+        "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+        "a3b6167473100ee06e0c796c2955552b",
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+    ]);
+}
+
+#[test]
+fn rfc4231_sha384() {
+    check::<Sha384>(&rfc4231_cases(), &[
+        "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6",
+        "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+        "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b2a5ab39dc13814b94e3ab6e101a34f27",
+        "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e6801dd23c4a7d679ccf8a386c674cffb",
+        "3abf34c3503b2a23a46efc619baef897",
+        "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952",
+        "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5a678cc31e799176d3860e6110c46523e",
+    ]);
+}
+
+#[test]
+fn rfc4231_sha512() {
+    check::<Sha512>(&rfc4231_cases(), &[
+        "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+        "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+        "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+        "415fad6271580a531d4179bc891d87a6",
+        "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+        "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+    ]);
+}
+
+#[test]
+fn rfc2202_md5() {
+    check::<Md5>(&rfc2202_cases(16), &[
+        "9294727a3638bb1c13f48ef8158bfc9d",
+        "750c783e6ab0b503eaa86e310a5db738",
+        "56be34521d144c88dbb8c733f0e8b3f6",
+        "697eaf0aca3a3aea3a75164746ffaa79",
+        "56461ef2342edc00f9bab995690efd4c",
+        "6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd",
+        "6f630fad67cda0ee1fb1f562db3aa53e",
+    ]);
+}
+
+#[test]
+fn rfc2202_sha1() {
+    check::<Sha1>(&rfc2202_cases(20), &[
+        "b617318655057264e28bc0b6fb378c8ef146be00",
+        "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+        "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+        "4c9007f4026250c6bc8414f9bf50c86c2d7235da",
+        "4c1a03424b55e07fe7f27be1d58bb9324a9a5a04",
+        "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+        "e8e99d0f45237d786d6bbaa7965c7808bbff1a91",
+    ]);
+}
+
+#[test]
+fn rfc2286_ripemd160() {
+    check::<Ripemd160>(&rfc2202_cases(20), &[
+        "24cb4bd67d20fc1a5d2ed7732dcc39377f0a5668",
+        "dda6c0213a485a9e24f4742064a7f033b43c4069",
+        "b0b105360de759960ab4f35298e116e295d8e7c1",
+        "d5ca862f4d21d5e610e18b4cf1beb97a4365ecf4",
+        "7619693978f91d90539ae786500ff3d8e0518e39",
+        "6466ca07ac5eac29e1bd523e5ada7605b791fd8b",
+        "69ea60798d71616cce5fd0871e23754cd75d5a0a",
+    ]);
+}
+
+#[test]
+fn verification() {
+    let tag = hmac::<Sha256>(b"Jefe", b"what do ya want for nothing?");
+
+    let mac = Hmac::<Sha256>::new(b"Jefe");
+    let mut right = mac.clone();
+    right.update(b"what do ya want for nothing?");
+    assert!(right.clone().verify(tag.as_ref()));
+
+    let mut wrong = mac.clone();
+    wrong.update(b"what do ya want for nothing!");
+    assert!(!wrong.verify(tag.as_ref()));
+
+    let mut tampered = *tag.as_bytes();
+    tampered[31] ^= 1;
+    assert!(!right.clone().verify(&tampered));
+
+    // A prefix only counts when asked for, and then not if it's too short
+    assert!(!right.clone().verify(&tag.as_ref()[..16]));
+    assert!(right.clone().verify_truncated(&tag.as_ref()[..16]));
+    assert!(!right.clone().verify_truncated(&tampered[..32]));
+    assert!(!right.verify_truncated(&tag.as_ref()[..15]));
+}
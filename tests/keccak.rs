@@ -0,0 +1,40 @@
+use hash_functions::keccak::{checksum_address, function_selector, keccak256, parse_address};
+
+// The examples from EIP-55 itself
+const EIP55_ADDRESSES: &[&str] = &[
+    "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+    "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+    "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+    "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+];
+
+#[test]
+fn empty_and_abc() {
+    assert_eq!(
+        keccak256(b"").to_string(),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert_eq!(
+        keccak256(b"abc").to_string(),
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+    );
+}
+
+#[test]
+fn eip55_checksums() {
+    for &address in EIP55_ADDRESSES {
+        let bytes = parse_address(&address.to_lowercase()).unwrap();
+        assert_eq!(checksum_address(&bytes), address);
+        assert_eq!(parse_address(address), Some(bytes));
+    }
+
+    // Flipping the case of one letter breaks the checksum
+    assert_eq!(parse_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"), None);
+    assert_eq!(parse_address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"), None);
+}
+
+#[test]
+fn selectors() {
+    assert_eq!(function_selector("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+    assert_eq!(function_selector("balanceOf(address)"), [0x70, 0xa0, 0x82, 0x31]);
+}
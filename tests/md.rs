@@ -0,0 +1,65 @@
+use hash_functions::md4::{md4, Md4};
+use hash_functions::md5::{md5, Md5};
+use hash_functions::sha1::{sha1, Sha1};
+use hash_functions::sha256::{sha256, Sha256};
+use hash_functions::sha512::{sha512, Sha512};
+use hash_functions::Digest;
+
+// Where the length stops fitting in the last block, for 64- and 128-byte
+// blocks, and where the blocks themselves end
+const BOUNDARIES: [usize; 8] = [55, 56, 63, 64, 111, 112, 127, 128];
+
+fn input(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i * 31 % 256) as u8).collect()
+}
+
+// Every way of cutting messages of each boundary length (and one past the
+// last) in two, and the boundaries as a sequence of cuts in one message,
+// against the one-shot function
+fn check<D: Digest>(oneshot: fn(&[u8]) -> D::Output) {
+    for length in BOUNDARIES.into_iter().chain([129, 200]) {
+        let data = input(length);
+        let expected = oneshot(&data);
+
+        for split in 0..=length {
+            let mut hasher = D::new();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.finalize().as_ref(), expected.as_ref(), "{}: {} split at {}", D::NAME, length, split);
+        }
+    }
+
+    let data = input(200);
+    let mut hasher = D::new();
+    let mut from = 0;
+    for to in BOUNDARIES.into_iter().chain([200]) {
+        hasher.update(&data[from..to]);
+        from = to;
+    }
+    assert_eq!(hasher.finalize().as_ref(), oneshot(&data).as_ref(), "{}: split at every boundary", D::NAME);
+}
+
+#[test]
+fn md4_splits() {
+    check::<Md4>(md4);
+}
+
+#[test]
+fn md5_splits() {
+    check::<Md5>(md5);
+}
+
+#[test]
+fn sha1_splits() {
+    check::<Sha1>(sha1);
+}
+
+#[test]
+fn sha256_splits() {
+    check::<Sha256>(sha256);
+}
+
+#[test]
+fn sha512_splits() {
+    check::<Sha512>(sha512);
+}
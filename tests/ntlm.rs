@@ -0,0 +1,122 @@
+use hash_functions::ntlm::{
+    blob, lmv2_response, nt_hash, nt_proof, ntlmv2_response, ntowf_v2, session_base_key, NetNtlmv2,
+    ParseNetNtlmv2Error,
+};
+use hash_functions::Output;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn nt_hashes() {
+    let cases = [
+        ("", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+        ("password", "8846f7eaee8fb117ad06bdd830b7586c"),
+        ("Password", "a4f49c406510bdcab6824ee7c30fd852"),
+        // Against OpenSSL's MD4 of the UTF-16LE
+        ("Pässwörd€", "04e9d4087e1303bea8e5239aa5ddd064"),
+    ];
+
+    for (password, expected) in cases {
+        assert_eq!(nt_hash(password).to_string(), expected, "{:?}", password);
+    }
+}
+
+// MS-NLMP section 4.2.4: user "User" in "Domain" with password "Password",
+// talking to "Server"
+#[test]
+fn ms_nlmp_example() {
+    let server_challenge = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+    let client_challenge = [0xaa; 8];
+
+    let mut target_info = vec![0x02, 0x00, 0x0c, 0x00];
+    target_info.extend("Domain".encode_utf16().flat_map(u16::to_le_bytes));
+    target_info.extend([0x01, 0x00, 0x0c, 0x00]);
+    target_info.extend("Server".encode_utf16().flat_map(u16::to_le_bytes));
+    target_info.extend([0x00, 0x00, 0x00, 0x00]);
+
+    let key = ntowf_v2(&nt_hash("Password"), "User", "Domain");
+    assert_eq!(key.to_string(), "0c868a403bfd7a93a3001ef22ef02e3f");
+
+    // The user name's case doesn't matter, but the domain's does
+    assert_eq!(ntowf_v2(&nt_hash("Password"), "uSER", "Domain"), key);
+    assert_ne!(ntowf_v2(&nt_hash("Password"), "User", "DOMAIN"), key);
+
+    let blob = blob(0, &client_challenge, &target_info);
+    assert_eq!(
+        hex(&blob),
+        "01010000000000000000000000000000aaaaaaaaaaaaaaaa00000000\
+         02000c0044006f006d00610069006e0001000c005300650072007600650072000000000000000000"
+    );
+
+    let proof = nt_proof(&key, &server_challenge, &blob);
+    assert_eq!(proof.to_string(), "68cd0ab851e51c96aabc927bebef6a1c");
+
+    let response = ntlmv2_response(&key, &server_challenge, &blob);
+    assert_eq!(response[..16], *proof.as_bytes());
+    assert_eq!(response[16..], blob[..]);
+
+    assert_eq!(session_base_key(&key, &proof).to_string(), "8de40ccadbc14a82f15cb0ad0de95ca3");
+
+    assert_eq!(
+        hex(&lmv2_response(&key, &server_challenge, &client_challenge)),
+        "86c35097ac9cec102554764a57cccc19aaaaaaaaaaaaaaaa"
+    );
+}
+
+// Worked out with Python's hmac and OpenSSL's MD4.  'ß' has no single
+// upper-case character, so stays as it is in the user name.
+const CAPTURED: &str = "straße.admin::CORP:1122334455667788:3ab51539337091b3f10cdd161139d91f:\
+                        0101000000000000f5e4d3c2b1a0d9010102030405060708000000000200080043004f00520050000000000000000000";
+
+#[test]
+fn net_ntlmv2() {
+    let captured: NetNtlmv2 = CAPTURED.parse().unwrap();
+    assert_eq!(captured.user, "straße.admin");
+    assert_eq!(captured.domain, "CORP");
+    assert_eq!(captured.server_challenge, [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88]);
+    assert_eq!(captured.to_string(), CAPTURED);
+
+    assert!(captured.verify("Pässwörd€"));
+    assert!(!captured.verify("Passwörd€"));
+    assert!(!captured.verify(""));
+
+    let hash: Output<16> = "04e9d4087e1303bea8e5239aa5ddd064".parse().unwrap();
+    assert!(captured.verify_nt_hash(&hash));
+    assert_eq!(captured.session_base_key(&hash).to_string(), "843fc2874739f7b0942fe1538e91e337");
+
+    // Capture tools print the hex in upper case as often as not
+    let upper: NetNtlmv2 = CAPTURED.replace("3ab5", "3AB5").parse().unwrap();
+    assert_eq!(upper, captured);
+
+    let mut tampered = captured.clone();
+    tampered.blob[8] ^= 1;
+    assert!(!tampered.verify("Pässwörd€"));
+}
+
+#[test]
+fn bad_net_ntlmv2_strings() {
+    use ParseNetNtlmv2Error::*;
+
+    let blob = "0101000000000000f5e4d3c2b1a0d9010102030405060708000000000000000000000000";
+    let cases = [
+        (format!("user:domain:1122334455667788:{}:{}", "00".repeat(16), blob), Format),
+        (format!("user::domain:1122334455667788:{}", "00".repeat(16)), Format),
+        (format!("::domain:1122334455667788:{}:{}", "00".repeat(16), blob), Format),
+        (format!("user::domain:11223344556677:{}:{}", "00".repeat(16), blob), Length),
+        (format!("user::domain:1122334455667788:{}:{}", "00".repeat(15), blob), Length),
+        (format!("user::domain:1122334455667788:{}:{}", "00".repeat(16), &blob[..54]), Length),
+        (format!("user::domain:1122334455667788:{}:{}0", "00".repeat(16), blob), Encoding),
+        (format!("user::domain:112233445566778g:{}:{}", "00".repeat(16), blob), Encoding),
+        (format!("user::domain:1122334455667788:{}:{}", "0x".repeat(16), blob), Encoding),
+    ];
+
+    for (s, error) in cases {
+        assert_eq!(s.parse::<NetNtlmv2>().err(), Some(error), "{}", s);
+    }
+
+    // The same with nothing wrong
+    let s = format!("user::domain:1122334455667788:{}:{}", "00".repeat(16), blob);
+    assert!(s.parse::<NetNtlmv2>().is_ok());
+}
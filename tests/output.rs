@@ -0,0 +1,113 @@
+use hash_functions::sha256::sha256;
+use hash_functions::{ct_eq, Output, ParseOutputError, VarOutput};
+
+const ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+#[test]
+fn round_trips() {
+    let digest = sha256(b"abc");
+    assert_eq!(digest.to_string(), ABC);
+    assert_eq!(digest.to_string().parse::<Output<32>>(), Ok(digest));
+
+    let bytes: [u8; 5] = [0x00, 0x0f, 0x10, 0xab, 0xff];
+    let output = Output::new(bytes);
+    assert_eq!(output.to_string(), "000f10abff");
+    assert_eq!(format!("{:x}", output), "000f10abff");
+    assert_eq!(format!("{:X}", output), "000F10ABFF");
+    assert_eq!("000f10abff".parse::<Output<5>>().unwrap().into_bytes(), bytes);
+}
+
+#[test]
+fn either_case() {
+    let lower: Output<32> = ABC.parse().unwrap();
+    let upper: Output<32> = ABC.to_uppercase().parse().unwrap();
+    let mixed: Output<32> = "BA7816bf8F01CFEA414140de5dae2223b00361a396177a9cb410ff61f20015AD".parse().unwrap();
+
+    assert_eq!(lower, sha256(b"abc"));
+    assert_eq!(upper, lower);
+    assert_eq!(mixed, lower);
+}
+
+#[test]
+fn bad_hex() {
+    // Too short, too long, and odd
+    assert_eq!(ABC[..62].parse::<Output<32>>(), Err(ParseOutputError::Length));
+    assert_eq!(format!("{}00", ABC).parse::<Output<32>>(), Err(ParseOutputError::Length));
+    assert_eq!(ABC[..63].parse::<Output<32>>(), Err(ParseOutputError::Length));
+    assert_eq!("".parse::<Output<32>>(), Err(ParseOutputError::Length));
+
+    // Right length, wrong characters: at each end, a space, a sign, and a
+    // character that takes more than one byte
+    for bad in [
+        format!("g{}", &ABC[1..]),
+        format!("{}g", &ABC[..63]),
+        format!(" {}", &ABC[1..]),
+        format!("+{}", &ABC[1..]),
+        format!("é{}", &ABC[2..]),
+    ] {
+        assert_eq!(bad.parse::<Output<32>>(), Err(ParseOutputError::Character), "{:?}", bad);
+    }
+}
+
+#[test]
+fn constant_time_equality() {
+    let a = sha256(b"abc");
+    let b = sha256(b"abd");
+
+    assert!(a.ct_eq(&a));
+    assert!(a.ct_eq(&sha256(b"abc")));
+    assert!(!a.ct_eq(&b));
+    assert_ne!(a, b);
+
+    // A difference in the last byte alone
+    let mut bytes = a.into_bytes();
+    bytes[31] ^= 1;
+    assert!(!a.ct_eq(&Output::new(bytes)));
+
+    assert!(ct_eq(b"abc", b"abc"));
+    assert!(!ct_eq(b"abc", b"abd"));
+    assert!(!ct_eq(b"abc", b"ab"));
+    assert!(ct_eq(b"", b""));
+}
+
+#[test]
+fn var_output() {
+    let short = VarOutput::<64>::new(&[0xde, 0xad]);
+    assert_eq!(short.len(), 2);
+    assert_eq!(short.as_bytes(), [0xde, 0xad]);
+    assert_eq!(short.to_string(), "dead");
+    assert_eq!(format!("{:X}", short), "DEAD");
+    assert_eq!("DEAD".parse::<VarOutput<64>>(), Ok(short));
+
+    // Anything from one byte to the maximum
+    for length in [1, 20, 33, 64] {
+        let bytes: Vec<u8> = (0..length as u8).collect();
+        let output = VarOutput::<64>::new(&bytes);
+        assert_eq!(output.len(), length);
+        assert_eq!(output.as_ref(), bytes);
+        assert_eq!(output.to_string().parse::<VarOutput<64>>(), Ok(output));
+    }
+
+    assert_eq!("".parse::<VarOutput<64>>(), Err(ParseOutputError::Length));
+    assert_eq!("abc".parse::<VarOutput<64>>(), Err(ParseOutputError::Length));
+    assert_eq!("00".repeat(65).parse::<VarOutput<64>>(), Err(ParseOutputError::Length));
+    assert_eq!("zz".parse::<VarOutput<64>>(), Err(ParseOutputError::Character));
+
+    // The same bytes at different lengths, and different bytes at the same
+    let long = VarOutput::<64>::new(&[0xde, 0xad, 0x00]);
+    assert!(!short.ct_eq(&long));
+    assert!(!short.ct_eq(&VarOutput::new(&[0xde, 0xae])));
+    assert!(short.ct_eq(&VarOutput::new(&[0xde, 0xad])));
+}
+
+#[test]
+#[should_panic]
+fn empty_var_output() {
+    VarOutput::<64>::new(&[]);
+}
+
+#[test]
+#[should_panic]
+fn oversized_var_output() {
+    VarOutput::<4>::new(&[0; 5]);
+}
@@ -0,0 +1,110 @@
+use hash_functions::pbkdf2::pbkdf2;
+use hash_functions::sha1::Sha1;
+use hash_functions::sha256::Sha256;
+use hash_functions::sha512::Sha512;
+use hash_functions::Digest;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn check<D: Digest + Clone>(password: &[u8], salt: &[u8], iterations: u32, expected: &str) {
+    let mut key = vec![0; expected.len() / 2];
+    pbkdf2::<D>(password, salt, iterations, &mut key);
+    assert_eq!(hex(&key), expected, "{} with {} iterations", D::NAME, iterations);
+}
+
+// RFC 6070 section 2
+#[test]
+fn rfc6070() {
+    check::<Sha1>(b"password", b"salt", 1, "0c60c80f961f0e71f3a9b524af6012062fe037a6");
+    check::<Sha1>(b"password", b"salt", 2, "ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957");
+    check::<Sha1>(b"password", b"salt", 4096, "4b007901b765489abead49d926f721d065a429c1");
+    check::<Sha1>(
+        b"passwordPASSWORDpassword",
+        b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+        4096,
+        "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038",
+    );
+    check::<Sha1>(b"pass\0word", b"sa\0lt", 4096, "56fa6aa75548099dcc37d7f03425e0c3");
+}
+
+// The last RFC 6070 case takes a while without optimization: run it with
+// `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn rfc6070_16777216_iterations() {
+    check::<Sha1>(b"password", b"salt", 16777216, "eefe3d61cd4da4e4e9945b3d6ba2158c2634e984");
+}
+
+// The same inputs with the longer hashes, checked against Python's
+// hashlib.pbkdf2_hmac.  The second longest output runs into a second block.
+
+#[test]
+fn sha256() {
+    check::<Sha256>(
+        b"password",
+        b"salt",
+        1,
+        "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b",
+    );
+    check::<Sha256>(
+        b"password",
+        b"salt",
+        2,
+        "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43",
+    );
+    check::<Sha256>(
+        b"password",
+        b"salt",
+        4096,
+        "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a",
+    );
+    check::<Sha256>(
+        b"passwordPASSWORDpassword",
+        b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+        4096,
+        "348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9",
+    );
+    check::<Sha256>(b"pass\0word", b"sa\0lt", 4096, "89b69d0516f829893c696226650a8687");
+}
+
+#[test]
+fn sha512() {
+    check::<Sha512>(
+        b"password",
+        b"salt",
+        1,
+        "867f70cf1ade02cff3752599a3a53dc4af34c7a669815ae5d513554e1c8cf252\
+         c02d470a285a0501bad999bfe943c08f050235d7d68b1da55e63f73b60a57fce",
+    );
+    check::<Sha512>(
+        b"password",
+        b"salt",
+        2,
+        "e1d9c16aa681708a45f5c7c4e215ceb66e011a2e9f0040713f18aefdb866d53c\
+         f76cab2868a39b9f7840edce4fef5a82be67335c77a6068e04112754f27ccf4e",
+    );
+    check::<Sha512>(
+        b"password",
+        b"salt",
+        4096,
+        "d197b1b33db0143e018b12f3d1d1479e6cdebdcc97c5c0f87f6902e072f457b5\
+         143f30602641b3d55cd335988cb36b84376060ecd532e039b742a239434af2d5",
+    );
+    check::<Sha512>(
+        b"passwordPASSWORDpassword",
+        b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+        4096,
+        "8c0511f4c6e597c6ac6315d8f0362e225f3c501495ba23b868c005174dc4ee71\
+         115b59f9e60cd9532fa33e0f75aefe30225c583a186cd82bd4daea9724a3d3b8\
+         04f75bdd41494fa324cab24bcc680fb3",
+    );
+    check::<Sha512>(b"pass\0word", b"sa\0lt", 4096, "9d9e9c4cd21fe4be24d5b8244c759665");
+}
+
+#[test]
+#[should_panic]
+fn zero_iterations() {
+    pbkdf2::<Sha256>(b"password", b"salt", 0, &mut [0; 32]);
+}
@@ -0,0 +1,191 @@
+use std::io::{Cursor, ErrorKind};
+
+use hash_functions::md4::md4;
+use hash_functions::rsync::{delta, patch, RollingChecksum, Signature, StrongHash};
+
+// Bytes that don't repeat in any block-sized stretch
+fn noise(length: usize, seed: u32) -> Vec<u8> {
+    let mut state = seed | 1;
+    (0..length)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+fn make_delta(basis: &[u8], new: &[u8], block_size: u32, strong_hash: StrongHash) -> Vec<u8> {
+    let signature = Signature::generate(basis, block_size, strong_hash).unwrap();
+    let mut changes = Vec::new();
+    delta(&signature, new, &mut changes).unwrap();
+    changes
+}
+
+fn apply(basis: &[u8], changes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut rebuilt = Vec::new();
+    patch(Cursor::new(basis), changes, &mut rebuilt)?;
+    Ok(rebuilt)
+}
+
+#[test]
+fn rolling_checksum() {
+    // 'a' + 'b' + 'c' at the bottom, 'a' + ('a' + 'b') + ('a' + 'b' + 'c') at
+    // the top
+    assert_eq!(RollingChecksum::new(b"abc").value(), 294 | 586 << 16);
+    assert_eq!(RollingChecksum::new(b"").value(), 0);
+
+    let data = noise(5000, 1);
+    let mut rolling = RollingChecksum::new(&data[..1000]);
+    for i in 0..4000 {
+        rolling.roll(data[i], data[i + 1000]);
+        assert_eq!(rolling, RollingChecksum::new(&data[i + 1..i + 1001]), "at {}", i);
+    }
+
+    for i in 4000..5000 {
+        rolling.shrink(data[i]);
+        assert_eq!(rolling, RollingChecksum::new(&data[i + 1..]), "at {}", i);
+    }
+}
+
+#[test]
+fn signature() {
+    let basis = noise(2500, 2);
+    let signature = Signature::generate(&basis[..], 1000, StrongHash::Md4).unwrap();
+
+    assert_eq!(signature.basis_length, 2500);
+    assert_eq!(signature.blocks.len(), 3);
+    assert_eq!(signature.blocks[1].weak, RollingChecksum::new(&basis[1000..2000]).value());
+    assert_eq!(signature.blocks[2].strong, md4(&basis[2000..]));
+
+    let mut written = Vec::new();
+    signature.write_to(&mut written).unwrap();
+    assert_eq!(written.len(), 4 + 1 + 4 + 8 + 3 * 20);
+    assert_eq!(written[..17], *b"hsig\x04\x00\x00\x03\xe8\x00\x00\x00\x00\x00\x00\x09\xc4");
+    assert_eq!(Signature::read_from(&written[..]).unwrap(), signature);
+
+    // Missing a block, with an extra byte, and with another magic number
+    for bad in [&written[..written.len() - 20], &[&written[..], &[0]].concat(), &written[1..]] {
+        assert!(Signature::read_from(bad).is_err());
+    }
+
+    let empty = Signature::generate(&b""[..], 700, StrongHash::Md5).unwrap();
+    assert!(empty.blocks.is_empty());
+}
+
+#[test]
+fn unchanged_file_is_one_copy() {
+    let basis = noise(10_000, 3);
+    let changes = make_delta(&basis, &basis, 1000, StrongHash::Md5);
+
+    let mut expected = b"hdlt\x05\x01".to_vec();
+    expected.extend(0u64.to_be_bytes());
+    expected.extend(10_000u32.to_be_bytes());
+    expected.push(0);
+    expected.extend(StrongHash::Md5.digest(&basis).as_ref());
+    assert_eq!(changes, expected);
+
+    assert_eq!(apply(&basis, &changes).unwrap(), basis);
+}
+
+#[test]
+fn round_trips() {
+    let basis = noise(20_000, 4);
+    let other = noise(3000, 5);
+
+    let inserted = [&basis[..7777], &other[..], &basis[7777..]].concat();
+    let deleted = [&basis[..5000], &basis[5500..]].concat();
+    let moved = [&basis[15_000..], &basis[..15_000]].concat();
+    let prepended = [&other[..5], &basis[..]].concat();
+    let appended = [&basis[..], &other[..]].concat();
+    let repeated = [&basis[..4000], &basis[..4000], &basis[..4000]].concat();
+    let mut changed = basis.clone();
+    changed[12_345] ^= 0x80;
+
+    let cases = [
+        ("unchanged", &basis[..], basis.len() / 100),
+        ("inserted", &inserted, other.len() + 2000),
+        ("deleted", &deleted, 2000),
+        ("moved", &moved, 2000),
+        ("prepended", &prepended, 100),
+        ("appended", &appended, other.len() + 100),
+        ("repeated", &repeated, 100),
+        ("changed", &changed, 1100),
+        ("replaced", &other, other.len() + 100),
+        ("emptied", &[], 100),
+    ];
+
+    for strong_hash in [StrongHash::Md4, StrongHash::Md5] {
+        for (name, new, most) in cases {
+            let changes = make_delta(&basis, new, 1000, strong_hash);
+            assert!(changes.len() <= most, "{}: {} bytes of delta", name, changes.len());
+            assert_eq!(apply(&basis, &changes).unwrap(), new, "{}", name);
+        }
+    }
+}
+
+#[test]
+fn block_sizes() {
+    let basis = noise(3000, 6);
+    let new = [&basis[..1234], b"something new", &basis[1500..]].concat();
+
+    for block_size in [1, 2, 7, 64, 1000, 2999, 3000, 3001, 100_000] {
+        let changes = make_delta(&basis, &new, block_size, StrongHash::Md5);
+        assert_eq!(apply(&basis, &changes).unwrap(), new, "{}-byte blocks", block_size);
+    }
+
+    // Nothing in the basis to find
+    let changes = make_delta(b"", &new, 100, StrongHash::Md5);
+    assert_eq!(apply(b"", &changes).unwrap(), new);
+    assert_eq!(apply(b"", &make_delta(b"", b"", 100, StrongHash::Md5)).unwrap(), b"");
+}
+
+// The last block of the basis is short, and only found at the end of the
+// new file
+#[test]
+fn short_last_block() {
+    let basis = noise(2300, 7);
+    let new = [&noise(500, 8)[..], &basis[2000..]].concat();
+
+    let changes = make_delta(&basis, &new, 1000, StrongHash::Md5);
+    assert!(changes.len() < 600);
+    assert_eq!(apply(&basis, &changes).unwrap(), new);
+}
+
+// Big enough for literals to be split up and the buffer to be refilled
+// many times over
+#[test]
+fn large_literals() {
+    let basis = noise(100_000, 9);
+    let new = [&noise(3 << 20, 10)[..], &basis[..]].concat();
+
+    let changes = make_delta(&basis, &new, 4096, StrongHash::Md4);
+    assert!(changes.len() < new.len() - 90_000);
+    assert_eq!(apply(&basis, &changes).unwrap(), new);
+}
+
+#[test]
+fn bad_deltas() {
+    let basis = noise(5000, 11);
+    let new = [&basis[..2000], b"new", &basis[2000..]].concat();
+    let changes = make_delta(&basis, &new, 500, StrongHash::Md5);
+    assert_eq!(apply(&basis, &changes).unwrap(), new);
+
+    // The wrong basis fails the checksum, or runs off its end
+    let mut other = basis.clone();
+    other[100] ^= 1;
+    assert_eq!(apply(&other, &changes).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(apply(&basis[..4000], &changes).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    assert_eq!(apply(&basis, &changes[..changes.len() - 1]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(apply(&basis, &changes[1..]).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    let mut unknown_command = changes.clone();
+    unknown_command[5] = 9;
+    assert_eq!(apply(&basis, &unknown_command).unwrap_err().kind(), ErrorKind::InvalidData);
+
+    let mut unknown_hash = changes.clone();
+    unknown_hash[4] = 6;
+    assert_eq!(apply(&basis, &unknown_hash).unwrap_err().kind(), ErrorKind::InvalidData);
+}
@@ -0,0 +1,31 @@
+use hash_functions::sha256::{sha224, Sha224};
+
+// The NIST example messages for FIPS 180-4, and the empty string
+#[test]
+fn sha224_vectors() {
+    assert_eq!(sha224(b"").to_string(), "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+    assert_eq!(sha224(b"abc").to_string(), "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7");
+    assert_eq!(
+        sha224(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+        "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"
+    );
+}
+
+// Messages either side of where the length stops fitting in the last block
+// and of the block end, cut in two everywhere
+#[test]
+fn sha224_update_splits() {
+    let data: Vec<u8> = (0..130).map(|i| (i * 13 % 256) as u8).collect();
+
+    for length in [55, 56, 57, 63, 64, 65, 119, 120, 128, 130] {
+        let message = &data[..length];
+        let expected = sha224(message);
+
+        for split in 0..=length {
+            let mut hasher = Sha224::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hasher.finalize(), expected, "{} bytes split at {}", length, split);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use hash_functions::sha3::{
+    sha3_224, sha3_256, sha3_384, sha3_512, shake128, shake256, Sha3_256, Shake128, Shake256,
+};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn input(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i % 251) as u8).collect()
+}
+
+// The NIST example values for FIPS 202
+#[test]
+fn sha3_empty_and_abc() {
+    assert_eq!(sha3_224(b"").to_string(), "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7");
+    assert_eq!(sha3_224(b"abc").to_string(), "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf");
+
+    assert_eq!(
+        sha3_256(b"").to_string(),
+        "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+    );
+    assert_eq!(
+        sha3_256(b"abc").to_string(),
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+    );
+
+    assert_eq!(
+        sha3_384(b"").to_string(),
+        "0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2ac3713831264adb47fb6bd1e058d5f004"
+    );
+    assert_eq!(
+        sha3_384(b"abc").to_string(),
+        "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"
+    );
+
+    assert_eq!(
+        sha3_512(b"").to_string(),
+        "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26"
+    );
+    assert_eq!(
+        sha3_512(b"abc").to_string(),
+        "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"
+    );
+}
+
+// Longer than a block of the rate (168 and 136 bytes), so that the output
+// runs through the permutation more than once
+const SHAKE128_EMPTY_200: &str = "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef263cb1eea988004b93103cfb0aeefd2a686e01fa4a58e8a3639ca8a1e3f9ae57e235b8cc873c23dc62b8d260169afa2f75ab916a58d974918835d25e6a435085b2badfd6dfaac359a5efbb7bcc4b59d538df9a04302e10c8bc1cbf1a0b3a5120ea17cda7cfad765f5623474d368ccca8af0007cd9f5e4c849f167a580b14aabdefaee7eef47cb0fca9767be1fda69419dfb927e9df07348b196691abaeb580b32def58538b8d23f877";
+
+const SHAKE256_ABC_300: &str = "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e41385141204f329979fd3047a13c5657724ada64d2470157b3cdc288620944d78dbcddbd912993f0913f164fb2ce95131a2d09a3e6d51cbfc622720d7a75c6334e8a2d7ec71a7cc29cf0ea610eeff1a588290a53000faa79932becec0bd3cd0b33a7e5d397fed1ada9442b99903f4dcfd8559ed3950faf40fe6f3b5d710ed3b677513771af6bfe11934817e8762d9896ba579d88d84ba7aa3cdc7055f6796f195bd9ae788f2f5bb96100d6bbaff7fbc6eea24d4449a2477d172a5507dcc931412fc346b1bb39b878330e026b12ddf384af3334560ea1d363966caa7d8ddcbec7da52b42215c11d5f8ee57f341";
+
+#[test]
+fn shake_past_one_block() {
+    assert_eq!(hex(&shake128(b"", 200)), SHAKE128_EMPTY_200);
+    assert_eq!(hex(&shake256(b"abc", 300)), SHAKE256_ABC_300);
+
+    // Shorter outputs are prefixes of longer ones
+    assert_eq!(hex(&shake128(b"", 32)), SHAKE128_EMPTY_200[..64]);
+}
+
+// Reads of any size, across the block boundaries, give the same stream
+#[test]
+fn repeated_reads() {
+    let mut hasher = Shake256::new();
+    hasher.update(b"abc");
+    let mut reader = hasher.finalize_xof();
+
+    let mut output = Vec::new();
+    for n in [1, 10, 125, 1, 136, 27] {
+        let mut piece = vec![0; n];
+        reader.read(&mut piece);
+        output.extend(piece);
+        assert_eq!(reader.position(), output.len() as u64);
+    }
+    assert_eq!(hex(&output), SHAKE256_ABC_300);
+
+    let mut reader = Shake128::new().finalize_xof();
+    let mut first = [0; 168];
+    let mut second = [0; 32];
+    reader.read(&mut first);
+    reader.read(&mut second);
+    assert_eq!(hex(&first) + &hex(&second), SHAKE128_EMPTY_200);
+}
+
+// Input fed in pieces that straddle the 136- and 168-byte rate boundaries
+// absorbs the same as input fed in at once
+#[test]
+fn incremental_across_rate_boundaries() {
+    let data = input(400);
+
+    for length in [135, 136, 137, 167, 168, 169, 272, 336, 400] {
+        let message = &data[..length];
+
+        for split in [1, 67, 135, 136, 137, 167, 168, 169, 271, 272, 273] {
+            let split = split.min(length);
+
+            let mut sha3 = Sha3_256::new();
+            sha3.update(&message[..split]);
+            sha3.update(&message[split..]);
+            assert_eq!(sha3.finalize(), sha3_256(message), "SHA3-256: {} bytes split at {}", length, split);
+
+            let mut shake = Shake128::new();
+            shake.update(&message[..split]);
+            shake.update(&message[split..]);
+            let mut output = [0; 200];
+            shake.finalize_xof().read(&mut output);
+            assert_eq!(output[..], shake128(message, 200), "SHAKE128: {} bytes split at {}", length, split);
+
+            let mut shake = Shake256::new();
+            for piece in message.chunks(split) {
+                shake.update(piece);
+            }
+            let mut output = [0; 200];
+            shake.finalize_xof().read(&mut output);
+            assert_eq!(output[..], shake256(message, 200), "SHAKE256: {} bytes in {}-byte pieces", length, split);
+        }
+    }
+}
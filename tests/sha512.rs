@@ -0,0 +1,63 @@
+use hash_functions::sha512::{
+    sha384, sha512, sha512_224, sha512_256, sha512_t, sha512_t_iv, InvalidTruncation, Sha512T,
+};
+
+// FIPS 180-4 sections 5.3.6.1 and 5.3.6.2
+#[test]
+fn published_ivs() {
+    assert_eq!(
+        sha512_t_iv(224),
+        Ok([
+            0x8C3D37C819544DA2, 0x73E1996689DCD4D6, 0x1DFAB7AE32FF9C82, 0x679DD514582F9FCF,
+            0x0F6D2B697BD44DA8, 0x77E36F7304C48942, 0x3F9D85A86A1D36C8, 0x1112E6AD91D692A1,
+        ])
+    );
+    assert_eq!(
+        sha512_t_iv(256),
+        Ok([
+            0x22312194FC2BF72C, 0x9F555FA3C84C64C2, 0x2393B86B6F53B151, 0x963877195940EABD,
+            0x96283EE2A88EFFE3, 0xBE5E1E2553863992, 0x2B0199FC2C85B8AA, 0x0EB72DDC81C52CA2,
+        ])
+    );
+}
+
+// The NIST example values for "abc"
+#[test]
+fn abc() {
+    assert_eq!(
+        sha384(b"abc").to_string(),
+        "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"
+    );
+    assert_eq!(
+        sha512(b"abc").to_string(),
+        "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+    );
+    assert_eq!(sha512_224(b"abc").to_string(), "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa");
+    assert_eq!(
+        sha512_256(b"abc").to_string(),
+        "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"
+    );
+}
+
+// The general form gives the same as the fixed ones, deriving its IVs
+#[test]
+fn general_form_matches_fixed() {
+    let message = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+    let mut hasher = Sha512T::new(256).unwrap();
+    hasher.update(&message[..100]);
+    hasher.update(&message[100..]);
+    assert_eq!(hasher.finalize().as_bytes(), sha512_256(message).as_bytes());
+
+    assert_eq!(sha512_t(message, 224).unwrap().as_bytes(), sha512_224(message).as_bytes());
+    assert_eq!(sha512_t(b"abc", 8).unwrap().len(), 1);
+}
+
+#[test]
+fn rejected_truncations() {
+    for t in [0, 1, 7, 100, 255, 384, 511, 512, 520, 1024] {
+        assert_eq!(sha512_t_iv(t), Err(InvalidTruncation(t)), "t = {}", t);
+        assert_eq!(Sha512T::new(t).err(), Some(InvalidTruncation(t)), "t = {}", t);
+        assert_eq!(sha512_t(b"abc", t).err(), Some(InvalidTruncation(t)), "t = {}", t);
+    }
+}
@@ -0,0 +1,86 @@
+use std::io::{self, Read};
+
+use hash_functions::blake2::Blake2b;
+use hash_functions::blake3::Blake3;
+use hash_functions::md5::Md5;
+use hash_functions::sha3::Sha3_256;
+use hash_functions::sha512::Sha512;
+use hash_functions::stream::{digest_file, digest_reader, read_chunks};
+use hash_functions::Digest;
+
+fn input() -> Vec<u8> {
+    (0..10_000).map(|i| (i * 7 + i / 256) as u8).collect()
+}
+
+fn matches_one_shot<D: Digest>()
+where
+    D::Output: PartialEq + std::fmt::Debug,
+{
+    let input = input();
+
+    // Buffer sizes either side of every block size in play, and one that
+    // swallows the whole input
+    for buffer_size in [1, 63, 64, 65, 127, 128, 1000, 1 << 16] {
+        let digest = digest_reader::<D, _>(&input[..], buffer_size).unwrap();
+        assert_eq!(digest, D::digest(&input), "{} with a {} byte buffer", D::NAME, buffer_size);
+    }
+}
+
+#[test]
+fn digest_reader_matches_digest() {
+    matches_one_shot::<Md5>();
+    matches_one_shot::<Sha512>();
+    matches_one_shot::<Sha3_256>();
+    matches_one_shot::<Blake2b>();
+    matches_one_shot::<Blake3>();
+}
+
+// Hands out at most 3 bytes a read, failing every other read with
+// Interrupted, which should just be retried.
+struct Stuttering<'a> {
+    input: &'a [u8],
+    interrupt: bool,
+}
+
+impl Read for Stuttering<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(io::Error::from(io::ErrorKind::Interrupted));
+        }
+
+        let n = buffer.len().min(3);
+        self.input.read(&mut buffer[..n])
+    }
+}
+
+#[test]
+fn short_and_interrupted_reads() {
+    let input = input();
+    let reader = Stuttering {
+        input: &input,
+        interrupt: false,
+    };
+
+    let mut total = 0;
+    read_chunks(reader, 16, |chunk| total += chunk.len()).unwrap();
+    assert_eq!(total, input.len());
+
+    let reader = Stuttering {
+        input: &input,
+        interrupt: false,
+    };
+    assert_eq!(digest_reader::<Md5, _>(reader, 16).unwrap(), Md5::digest(&input));
+}
+
+#[test]
+fn errors_are_returned() {
+    let error = digest_file::<Md5, _>("/nonexistent/file").unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+#[should_panic(expected = "Buffer size must be nonzero")]
+fn zero_buffer_size() {
+    let _ = digest_reader::<Md5, _>(&b"abc"[..], 0);
+}